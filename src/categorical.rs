@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+// ---------------------------
+// 分类取值解析
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    Known(usize), // 词表中的编号
+    Unknown,      // 允许未知取值时，无法识别的值归入这一类
+}
+
+impl Category {
+    pub fn code(self) -> Option<usize> {
+        match self {
+            Category::Known(code) => Some(code),
+            Category::Unknown => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryError {
+    pub vocabulary: String,
+    pub value: String,
+    pub expected: Vec<String>,
+}
+
+impl fmt::Display for CategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "无法识别的 {} 取值 '{}'，可选值: {}",
+            self.vocabulary,
+            self.value,
+            self.expected.join(", ")
+        )
+    }
+}
+
+impl Error for CategoryError {}
+
+// 一个分类列的完整词表: 规范取值 + 同义词。
+// 匹配时忽略大小写和首尾空白，但不做子串匹配（"female" 不会被当作 "male"）。
+#[derive(Debug, Clone)]
+pub struct Vocabulary {
+    name: String,
+    labels: Vec<String>,
+    lookup: HashMap<String, usize>,
+    allow_unknown: bool,
+}
+
+fn normalize(value: &str) -> String {
+    value.trim().to_lowercase()
+}
+
+impl Vocabulary {
+    pub fn new(name: &str, labels: &[&str]) -> Self {
+        let mut lookup = HashMap::new();
+        for (code, label) in labels.iter().enumerate() {
+            lookup.insert(normalize(label), code);
+        }
+        Vocabulary {
+            name: name.to_string(),
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+            lookup,
+            allow_unknown: false,
+        }
+    }
+
+    // 增加同义词，label 必须是词表中已有的规范取值
+    pub fn with_synonym(mut self, synonym: &str, label: &str) -> Self {
+        let code = self
+            .labels
+            .iter()
+            .position(|l| l == label)
+            .unwrap_or_else(|| panic!("{} 词表中没有取值 '{}'", self.name, label));
+        self.lookup.insert(normalize(synonym), code);
+        self
+    }
+
+    // 无法识别的值返回 Category::Unknown 而不是错误
+    pub fn allow_unknown(mut self) -> Self {
+        self.allow_unknown = true;
+        self
+    }
+
+    // 性别: male = 0, female = 1
+    pub fn sex() -> Self {
+        Vocabulary::new("sex", &["male", "female"])
+            .with_synonym("m", "male")
+            .with_synonym("man", "male")
+            .with_synonym("男", "male")
+            .with_synonym("男性", "male")
+            .with_synonym("f", "female")
+            .with_synonym("woman", "female")
+            .with_synonym("女", "female")
+            .with_synonym("女性", "female")
+    }
+
+    // 舱位等级: "1" = 0, "2" = 1, "3" = 2
+    pub fn pclass() -> Self {
+        Vocabulary::new("pclass", &["1", "2", "3"])
+            .with_synonym("1st", "1")
+            .with_synonym("first", "1")
+            .with_synonym("头等舱", "1")
+            .with_synonym("2nd", "2")
+            .with_synonym("second", "2")
+            .with_synonym("二等舱", "2")
+            .with_synonym("3rd", "3")
+            .with_synonym("third", "3")
+            .with_synonym("三等舱", "3")
+    }
+
+    // 登船港口: S = Southampton, C = Cherbourg, Q = Queenstown
+    pub fn embarked() -> Self {
+        Vocabulary::new("embarked", &["S", "C", "Q"])
+            .with_synonym("southampton", "S")
+            .with_synonym("cherbourg", "C")
+            .with_synonym("queenstown", "Q")
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn labels(&self) -> &[String] {
+        &self.labels
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    pub fn label(&self, code: usize) -> Option<&str> {
+        self.labels.get(code).map(|s| s.as_str())
+    }
+
+    pub fn parse(&self, value: &str) -> Result<Category, CategoryError> {
+        match self.lookup.get(&normalize(value)) {
+            Some(&code) => Ok(Category::Known(code)),
+            None if self.allow_unknown => Ok(Category::Unknown),
+            None => Err(CategoryError {
+                vocabulary: self.name.clone(),
+                value: value.trim().to_string(),
+                expected: self.labels.clone(),
+            }),
+        }
+    }
+}
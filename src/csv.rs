@@ -0,0 +1,326 @@
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead};
+
+// ---------------------------
+// CSV 记录与错误类型 (RFC 4180)
+// ---------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRecord {
+    pub line: usize, // 记录开始的物理行号（从1开始）
+    pub fields: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum CsvErrorKind {
+    Io(io::Error),
+    UnexpectedQuote,    // 未加引号的字段中间出现了引号
+    TrailingCharacters, // 闭合引号之后既不是逗号也不是行尾
+    UnterminatedQuote,  // 到文件末尾引号仍未闭合
+    FieldCount { expected: usize, found: usize }, // 字段数与标题行不一致（由调用方检查）
+}
+
+#[derive(Debug)]
+pub struct CsvError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: CsvErrorKind,
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match &self.kind {
+            CsvErrorKind::Io(e) => format!("读取失败: {}", e),
+            CsvErrorKind::UnexpectedQuote => "未加引号的字段中出现引号".to_string(),
+            CsvErrorKind::TrailingCharacters => "闭合引号后出现多余字符".to_string(),
+            CsvErrorKind::UnterminatedQuote => "引号直到文件结尾都没有闭合".to_string(),
+            CsvErrorKind::FieldCount { expected, found } => {
+                format!("应有 {} 个字段，实际 {} 个", expected, found)
+            }
+        };
+        write!(f, "CSV格式错误 (第 {} 行, 第 {} 列): {}", self.line, self.column, reason)
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            CsvErrorKind::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// ---------------------------
+// CSV 读取器
+// ---------------------------
+// 支持: 带引号的字段、"" 转义、字段内换行、CRLF 行尾、UTF-8 BOM。
+// 空行和只含空白字符的行会被跳过；出错后从下一物理行重新开始解析。
+pub struct CsvReader<R> {
+    reader: R,
+    line: usize,
+    buf: String,
+    done: bool,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    QuoteInQuoted, // 引号字段中刚读到一个引号，可能是转义也可能是闭合
+}
+
+impl<R: BufRead> CsvReader<R> {
+    pub fn new(reader: R) -> Self {
+        CsvReader {
+            reader,
+            line: 0,
+            buf: String::new(),
+            done: false,
+        }
+    }
+
+    // 读取下一物理行到 buf，返回去掉的行尾 ("\r\n" / "\n" / "")，文件结束时返回 None
+    fn next_line(&mut self) -> Result<Option<&'static str>, CsvError> {
+        self.buf.clear();
+        let n = match self.reader.read_line(&mut self.buf) {
+            Ok(n) => n,
+            Err(e) => {
+                // 非 UTF-8 的行已经被消费掉，行号照常前进，后续行仍可继续解析
+                if e.kind() == io::ErrorKind::InvalidData {
+                    self.line += 1;
+                }
+                return Err(CsvError { line: self.line.max(1), column: 1, kind: CsvErrorKind::Io(e) });
+            }
+        };
+        if n == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        if self.line == 1 && self.buf.starts_with('\u{feff}') {
+            self.buf.drain(..'\u{feff}'.len_utf8());
+        }
+        let ending = if self.buf.ends_with("\r\n") {
+            "\r\n"
+        } else if self.buf.ends_with('\n') {
+            "\n"
+        } else {
+            ""
+        };
+        self.buf.truncate(self.buf.len() - ending.len());
+        Ok(Some(ending))
+    }
+
+    fn read_record(&mut self) -> Result<Option<CsvRecord>, CsvError> {
+        // 跳过空行和只含空白的行
+        let mut ending = loop {
+            match self.next_line()? {
+                None => return Ok(None),
+                Some(ending) if !self.buf.trim().is_empty() => break ending,
+                Some(_) => continue,
+            }
+        };
+
+        let start_line = self.line;
+        let mut fields = Vec::new();
+        let mut field = String::new();
+        let mut state = State::FieldStart;
+        let mut quote_pos = (start_line, 1);
+
+        loop {
+            for (i, c) in self.buf.chars().enumerate() {
+                let column = i + 1;
+                state = match (state, c) {
+                    (State::FieldStart, '"') => {
+                        quote_pos = (self.line, column);
+                        State::Quoted
+                    }
+                    (State::FieldStart, ',') | (State::Unquoted, ',') | (State::QuoteInQuoted, ',') => {
+                        fields.push(std::mem::take(&mut field));
+                        State::FieldStart
+                    }
+                    (State::Unquoted, '"') => {
+                        return Err(CsvError { line: self.line, column, kind: CsvErrorKind::UnexpectedQuote });
+                    }
+                    (State::FieldStart, c) | (State::Unquoted, c) => {
+                        field.push(c);
+                        State::Unquoted
+                    }
+                    (State::Quoted, '"') => State::QuoteInQuoted,
+                    (State::Quoted, c) => {
+                        field.push(c);
+                        State::Quoted
+                    }
+                    (State::QuoteInQuoted, '"') => {
+                        field.push('"');
+                        State::Quoted
+                    }
+                    (State::QuoteInQuoted, _) => {
+                        return Err(CsvError { line: self.line, column, kind: CsvErrorKind::TrailingCharacters });
+                    }
+                };
+            }
+
+            if state != State::Quoted {
+                break;
+            }
+
+            // 引号字段跨行：保留原始换行符并继续读取下一行
+            field.push_str(ending);
+            ending = match self.next_line()? {
+                Some(ending) => ending,
+                None => {
+                    self.done = true;
+                    return Err(CsvError {
+                        line: quote_pos.0,
+                        column: quote_pos.1,
+                        kind: CsvErrorKind::UnterminatedQuote,
+                    });
+                }
+            };
+        }
+
+        fields.push(field);
+        Ok(Some(CsvRecord { line: start_line, fields }))
+    }
+}
+
+impl<R: BufRead> Iterator for CsvReader<R> {
+    type Item = Result<CsvRecord, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.read_record() {
+            Ok(Some(record)) => Some(Ok(record)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                if let CsvErrorKind::Io(io_err) = &e.kind {
+                    if io_err.kind() != io::ErrorKind::InvalidData {
+                        self.done = true;
+                    }
+                }
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(input: &[u8]) -> Vec<Result<CsvRecord, CsvError>> {
+        CsvReader::new(input).collect()
+    }
+
+    fn fields(input: &[u8]) -> Vec<Vec<String>> {
+        read(input).into_iter().map(|r| r.expect("应能解析").fields).collect()
+    }
+
+    fn error(input: &[u8]) -> CsvError {
+        read(input)
+            .into_iter()
+            .find_map(Result::err)
+            .expect("应产生错误")
+    }
+
+    #[test]
+    fn plain_fields() {
+        assert_eq!(fields(b"a,b,c\n1,,3\n"), vec![vec!["a", "b", "c"], vec!["1", "", "3"]]);
+    }
+
+    #[test]
+    fn quoted_fields_keep_commas() {
+        assert_eq!(fields(b"\"Braund, Mr. Owen\",male\n"), vec![vec!["Braund, Mr. Owen", "male"]]);
+    }
+
+    #[test]
+    fn doubled_quotes_are_escapes() {
+        assert_eq!(fields(b"\"say \"\"hi\"\"\",x\n\"\"\"\"\n"), vec![vec!["say \"hi\"", "x"], vec!["\""]]);
+    }
+
+    #[test]
+    fn embedded_newlines_keep_start_line() {
+        let records: Vec<CsvRecord> = read(b"h\n\"one\ntwo\r\nthree\",x\nlast\n").into_iter().map(Result::unwrap).collect();
+        assert_eq!(records[1].fields, vec!["one\ntwo\r\nthree", "x"]);
+        assert_eq!(records[1].line, 2);
+        assert_eq!(records[2].line, 5);
+    }
+
+    #[test]
+    fn crlf_line_endings() {
+        assert_eq!(fields(b"a,b\r\n1,2\r\n"), vec![vec!["a", "b"], vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn utf8_bom_is_stripped() {
+        assert_eq!(fields(b"\xEF\xBB\xBFsurvived,sex\n"), vec![vec!["survived", "sex"]]);
+    }
+
+    #[test]
+    fn blank_and_whitespace_lines_are_skipped() {
+        let records: Vec<CsvRecord> = read(b"a,b\n\n   \r\n\t\n1,2\n").into_iter().map(Result::unwrap).collect();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].fields, vec!["1", "2"]);
+        assert_eq!(records[1].line, 5);
+    }
+
+    #[test]
+    fn last_line_without_newline() {
+        assert_eq!(fields(b"a,b\n1,2"), vec![vec!["a", "b"], vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn unexpected_quote_position() {
+        let e = error(b"a,b\nab\"c,d\n");
+        assert!(matches!(e.kind, CsvErrorKind::UnexpectedQuote));
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+
+    #[test]
+    fn trailing_characters_position() {
+        let e = error(b"\"ab\"x,c\n");
+        assert!(matches!(e.kind, CsvErrorKind::TrailingCharacters));
+        assert_eq!((e.line, e.column), (1, 5));
+    }
+
+    #[test]
+    fn unterminated_quote_points_at_opening_quote() {
+        let results = read(b"a,b\n1,\"open\nstill open\n");
+        assert_eq!(results.len(), 2);
+        let e = results[1].as_ref().unwrap_err();
+        assert!(matches!(e.kind, CsvErrorKind::UnterminatedQuote));
+        assert_eq!((e.line, e.column), (2, 3));
+    }
+
+    #[test]
+    fn reading_resumes_after_a_bad_line() {
+        let results = read(b"a\"b\nok\n");
+        assert!(results[0].is_err());
+        assert_eq!(results[1].as_ref().unwrap().fields, vec!["ok"]);
+    }
+
+    #[test]
+    fn invalid_utf8_is_an_io_error_and_reading_continues() {
+        let results = read(b"a,b\n\xFF\xFE,1\n2,3\n");
+        let e = results[1].as_ref().unwrap_err();
+        assert!(matches!(&e.kind, CsvErrorKind::Io(io_err) if io_err.kind() == io::ErrorKind::InvalidData));
+        assert_eq!(e.line, 2);
+        assert!(e.source().is_some());
+        assert_eq!(results[2].as_ref().unwrap().fields, vec!["2", "3"]);
+        assert_eq!(results[2].as_ref().unwrap().line, 3);
+    }
+
+    #[test]
+    fn field_count_error_message() {
+        let e = CsvError { line: 3, column: 1, kind: CsvErrorKind::FieldCount { expected: 8, found: 7 } };
+        assert_eq!(e.to_string(), "CSV格式错误 (第 3 行, 第 1 列): 应有 8 个字段，实际 7 个");
+        assert!(e.source().is_none());
+    }
+}
@@ -0,0 +1,135 @@
+use std::fmt;
+
+use crate::dataset::DataSet;
+use crate::metrics::{accuracy_score, f1_score, log_loss, precision_score, recall_score};
+use crate::model::Classifier;
+use crate::split::CvStrategy;
+
+// ---------------------------
+// 交叉验证
+// ---------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoldScore {
+    pub train_size: usize,
+    pub test_size: usize,
+    pub accuracy: f64,
+    pub precision: f64,
+    pub recall: f64,
+    pub f1: f64,
+    pub log_loss: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    pub std: f64, // 总体标准差
+}
+
+impl Summary {
+    pub fn of(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Summary { mean: 0.0, std: 0.0 };
+        }
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        Summary { mean, std: var.sqrt() }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.4} ± {:.4}", self.mean, self.std)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CvReport {
+    pub folds: Vec<FoldScore>,
+}
+
+impl CvReport {
+    fn summary(&self, metric: impl Fn(&FoldScore) -> f64) -> Summary {
+        let values: Vec<f64> = self.folds.iter().map(metric).collect();
+        Summary::of(&values)
+    }
+
+    pub fn accuracy(&self) -> Summary {
+        self.summary(|f| f.accuracy)
+    }
+
+    pub fn precision(&self) -> Summary {
+        self.summary(|f| f.precision)
+    }
+
+    pub fn recall(&self) -> Summary {
+        self.summary(|f| f.recall)
+    }
+
+    pub fn f1(&self) -> Summary {
+        self.summary(|f| f.f1)
+    }
+
+    pub fn log_loss(&self) -> Summary {
+        self.summary(|f| f.log_loss)
+    }
+}
+
+impl fmt::Display for CvReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>5} {:>6} {:>6} {:>9} {:>9} {:>9} {:>9} {:>9}",
+            "fold", "train", "test", "accuracy", "precision", "recall", "f1", "log_loss"
+        )?;
+        // 折数很多（如留一法）时只列出前 10 折
+        for (i, s) in self.folds.iter().enumerate().take(10) {
+            writeln!(
+                f,
+                "{:>5} {:>6} {:>6} {:>9.4} {:>9.4} {:>9.4} {:>9.4} {:>9.4}",
+                i + 1, s.train_size, s.test_size, s.accuracy, s.precision, s.recall, s.f1, s.log_loss
+            )?;
+        }
+        if self.folds.len() > 10 {
+            writeln!(f, "  ... 共 {} 折", self.folds.len())?;
+        }
+        writeln!(f, "accuracy:  {}", self.accuracy())?;
+        writeln!(f, "precision: {}", self.precision())?;
+        writeln!(f, "recall:    {}", self.recall())?;
+        writeln!(f, "f1:        {}", self.f1())?;
+        write!(f, "log_loss:  {}", self.log_loss())
+    }
+}
+
+// 每一折都用 factory 新建一个模型，在训练部分上拟合，在测试部分上评估
+pub fn cross_validate<M, F>(dataset: &DataSet, strategy: &CvStrategy, mut factory: F) -> CvReport
+where
+    M: Classifier,
+    F: FnMut() -> M,
+{
+    let folds = strategy
+        .folds(dataset)
+        .into_iter()
+        .map(|fold| {
+            let train = dataset.subset(&fold.train);
+            let test = dataset.subset(&fold.test);
+
+            let mut model = factory();
+            model.set_feature_names(&train.feature_names);
+            model.fit(&train.features, &train.labels);
+            let proba = model.predict_proba(&test.features);
+            let pred = model.predict(&test.features);
+
+            FoldScore {
+                train_size: train.len(),
+                test_size: test.len(),
+                accuracy: accuracy_score(&test.labels, &pred),
+                precision: precision_score(&test.labels, &pred),
+                recall: recall_score(&test.labels, &pred),
+                f1: f1_score(&test.labels, &pred),
+                log_loss: log_loss(&test.labels, &proba),
+            }
+        })
+        .collect();
+    CvReport { folds }
+}
@@ -0,0 +1,622 @@
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::categorical::Vocabulary;
+use crate::csv::{CsvError, CsvErrorKind, CsvReader};
+use crate::error::DataError;
+use crate::frame::{DataFrame, DataType, FrameError, Series};
+use crate::matrix::Matrix;
+use crate::passenger::{Passenger, Pclass, Sex};
+use crate::quality::QualityReport;
+use crate::schema::{Column, ColumnMap, Schema, SchemaError};
+use crate::text::add_name_features;
+
+// ---------------------------
+// 数据结构
+// ---------------------------
+// features 每一行的列顺序
+pub const FEATURE_NAMES: [&str; 7] = ["pclass", "sex", "age", "fare", "sibsp", "parch", "family_size"];
+
+pub fn feature_index(name: &str) -> Option<usize> {
+    FEATURE_NAMES.iter().position(|&f| f == name)
+}
+
+// 原始字段值（只保留 schema 认识的列），用于数据质量检查。
+// 加载时被跳过的记录不在 rows 中：字段齐全的保留在 rejected 里，以便统计是哪一列出的问题；
+// CSV 格式错误或字段数不足、无法按列拆分的记录只计数
+#[derive(Debug, Clone, Default)]
+pub struct RawTable {
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<String>>,
+    pub rejected: Vec<Vec<String>>,
+    pub unreadable: usize,
+}
+
+impl RawTable {
+    pub fn get(&self, row: usize, column: Column) -> Option<&str> {
+        let j = self.columns.iter().position(|&c| c == column)?;
+        self.rows.get(row).map(|r| r[j].as_str())
+    }
+}
+
+// passenger_ids、features、frame、raw.rows 一一对应；无标签数据（如 Kaggle test.csv）的 labels 为空。
+// frame 是解析后的带类型乘客数据（缺失和无法解析的值为空值），features 由 feature_matrix 从 frame 得到，
+// 还可以用 add_frame_features 追加 frame 中的其它列（如姓名派生的 title）。
+#[derive(Debug)]
+pub struct DataSet {
+    pub passenger_ids: Vec<u32>,
+    pub features: Matrix,
+    pub feature_names: Vec<String>, // features 各列的名称
+    pub labels: Vec<f64>,
+    pub frame: DataFrame,
+    pub raw: RawTable,
+    pub vocabularies: Vocabularies, // 加载时使用的词表，数据质量检查按同一套词表判断
+}
+
+impl DataSet {
+    // 按行下标取子集（顺序与 indices 一致）
+    pub fn subset(&self, indices: &[usize]) -> DataSet {
+        DataSet {
+            passenger_ids: indices.iter().map(|&i| self.passenger_ids[i]).collect(),
+            features: self.features.select_rows(indices),
+            feature_names: self.feature_names.clone(),
+            labels: if self.labels.is_empty() {
+                Vec::new()
+            } else {
+                indices.iter().map(|&i| self.labels[i]).collect()
+            },
+            frame: self.frame.take(indices),
+            raw: RawTable {
+                columns: self.raw.columns.clone(),
+                rows: indices.iter().map(|&i| self.raw.rows[i].clone()).collect(),
+                // 跳过的记录属于整个文件，不属于任何子集
+                rejected: Vec::new(),
+                unreadable: 0,
+            },
+            vocabularies: self.vocabularies.clone(),
+        }
+    }
+
+    // 把乘客表中的列追加到特征矩阵末尾。数值和布尔列取原值，分类列取字典编号，空值为 NaN。
+    // 分类编号只有在字典固定时（如 title）才能在训练数据和待预测数据之间对应，
+    // surname 这类按数据内容建立字典的列不适合直接追加。
+    pub fn add_frame_features(&mut self, names: &[&str]) -> Result<(), FrameError> {
+        let mut columns = Vec::with_capacity(names.len());
+        for &name in names {
+            let series = self.frame.column(name)?;
+            let values: Vec<f64> = if series.dtype() == DataType::Categorical {
+                (0..series.len()).map(|i| series.code_at(i).map_or(f64::NAN, |c| c as f64)).collect()
+            } else {
+                series.to_f64()?.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect()
+            };
+            columns.push(values);
+        }
+
+        let (rows, cols) = (self.features.rows(), self.features.cols() + columns.len());
+        let mut data = Vec::with_capacity(rows * cols);
+        for (i, row) in self.features.iter_rows().enumerate() {
+            data.extend_from_slice(row);
+            data.extend(columns.iter().map(|column| column[i]));
+        }
+        self.features = Matrix::from_vec(rows, cols, data).expect("追加列后的形状由构造保证");
+        self.feature_names.extend(names.iter().map(|name| name.to_string()));
+        Ok(())
+    }
+
+    pub fn quality_report(&self) -> QualityReport {
+        QualityReport::from_dataset(self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.features.rows()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.features.is_empty()
+    }
+
+    // 是否带有 survived 标签，可以用于训练和评估
+    pub fn is_labeled(&self) -> bool {
+        self.labels.len() == self.features.rows() && !self.is_empty()
+    }
+}
+
+// ---------------------------
+// 从乘客表得到特征矩阵
+// ---------------------------
+// 乘客表的每一行转换为 Passenger（亲属数缺失时为 0）
+pub fn passengers(frame: &DataFrame) -> Result<Vec<Passenger>, FrameError> {
+    let pclass = frame.column(Column::Pclass.name())?;
+    let sex = frame.column(Column::Sex.name())?;
+    let age = frame.column(Column::Age.name())?.to_f64()?;
+    let fare = frame.column(Column::Fare.name())?.to_f64()?;
+    let sibsp = frame.column(Column::SibSp.name())?.to_f64()?;
+    let parch = frame.column(Column::Parch.name())?.to_f64()?;
+
+    let categorical = |series: &Series| FrameError::TypeMismatch {
+        column: series.name().to_string(),
+        expected: DataType::Categorical,
+        found: series.dtype(),
+    };
+    (0..frame.height())
+        .map(|i| {
+            Ok(Passenger {
+                pclass: pclass
+                    .code_at(i)
+                    .and_then(|c| Pclass::from_code(c as usize))
+                    .ok_or_else(|| categorical(pclass))?,
+                sex: sex.code_at(i).and_then(|c| Sex::from_code(c as usize)).ok_or_else(|| categorical(sex))?,
+                age: age[i],
+                fare: fare[i],
+                sibsp: sibsp[i].unwrap_or(0.0) as u32,
+                parch: parch[i].unwrap_or(0.0) as u32,
+            })
+        })
+        .collect()
+}
+
+// 按 FEATURE_NAMES 的顺序生成特征。缺失的年龄和票价保留为 NaN，
+// 由 Pipeline 中的填充步骤在训练数据上学习填充值，避免测试数据泄漏到训练中。
+pub fn feature_matrix(frame: &DataFrame) -> Result<Matrix, FrameError> {
+    let mut features = Matrix::zeros(frame.height(), FEATURE_NAMES.len());
+    for (i, passenger) in passengers(frame)?.iter().enumerate() {
+        features.row_mut(i).copy_from_slice(&passenger.to_features());
+    }
+    Ok(features)
+}
+
+// ---------------------------
+// 解析模式与加载报告
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    Strict,  // 遇到第一个有问题的记录或字段就返回错误
+    Lenient, // 记录所有问题: 坏记录跳过，坏数值字段按缺失处理
+}
+
+// 分类列的词表。可以换成带更多同义词的词表，但 pclass、sex 的规范取值及其顺序
+// 必须与 Pclass、Sex 的编号一致
+#[derive(Debug, Clone)]
+pub struct Vocabularies {
+    pub pclass: Vocabulary,
+    pub sex: Vocabulary,
+    pub embarked: Vocabulary,
+}
+
+impl Default for Vocabularies {
+    fn default() -> Self {
+        Vocabularies {
+            pclass: Vocabulary::pclass(),
+            sex: Vocabulary::sex(),
+            embarked: Vocabulary::embarked(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadOptions {
+    pub mode: ParseMode,
+    pub vocabularies: Vocabularies,
+}
+
+impl LoadOptions {
+    pub fn new(mode: ParseMode) -> Self {
+        LoadOptions { mode, vocabularies: Vocabularies::default() }
+    }
+
+    pub fn vocabularies(mut self, vocabularies: Vocabularies) -> Self {
+        let same_labels = |a: &Vocabulary, b: Vocabulary| a.labels() == b.labels();
+        assert!(
+            same_labels(&vocabularies.pclass, Vocabulary::pclass()) && same_labels(&vocabularies.sex, Vocabulary::sex()),
+            "pclass、sex 词表的规范取值必须与 Pclass、Sex 的编号一致"
+        );
+        self.vocabularies = vocabularies;
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct LoadReport {
+    pub mode: ParseMode,
+    pub records_read: usize,
+    pub records_skipped: usize,
+    pub issues: Vec<DataError>,
+}
+
+impl LoadReport {
+    fn new(mode: ParseMode) -> Self {
+        LoadReport {
+            mode,
+            records_read: 0,
+            records_skipped: 0,
+            issues: Vec::new(),
+        }
+    }
+
+    // 严格模式下直接返回错误；宽松模式下记录问题，由调用方决定如何继续
+    fn note(&mut self, error: DataError) -> Result<(), DataError> {
+        match self.mode {
+            ParseMode::Strict => Err(error),
+            ParseMode::Lenient => {
+                self.issues.push(error);
+                Ok(())
+            }
+        }
+    }
+
+    // 记录问题并跳过当前记录
+    fn skip(&mut self, error: DataError) -> Result<(), DataError> {
+        self.note(error)?;
+        self.records_skipped += 1;
+        Ok(())
+    }
+
+    // 训练数据可以跳过坏记录；待预测数据跳过一条就意味着提交文件少一名乘客，所以直接报错
+    fn reject(&mut self, labeled: bool, error: DataError) -> Result<(), DataError> {
+        if labeled {
+            self.skip(error)
+        } else {
+            Err(error)
+        }
+    }
+}
+
+fn unparsable(columns: &ColumnMap, line: usize, column: Column, value: &str, reason: String) -> DataError {
+    DataError::UnparsableField {
+        line,
+        column: columns.index(column).map_or(0, |i| i + 1),
+        field: column.name().to_string(),
+        value: value.to_string(),
+        reason,
+    }
+}
+
+// 数值字段: 空字段返回 Ok(None)，无法解析时返回 UnparsableField
+fn parse_number<T>(columns: &ColumnMap, row: &[String], line: usize, column: Column) -> Result<Option<T>, DataError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match columns.value(row, column) {
+        None => Ok(None),
+        Some(raw) => raw
+            .parse::<T>()
+            .map(Some)
+            .map_err(|e| unparsable(columns, line, column, raw, e.to_string())),
+    }
+}
+
+// 亲属数: 必须是非负整数（允许 "1.0" 这样的写法），负数和小数视为无法解析
+fn parse_count(columns: &ColumnMap, row: &[String], line: usize, column: Column) -> Result<Option<f64>, DataError> {
+    match parse_number::<f64>(columns, row, line, column)? {
+        Some(v) if v < 0.0 || v.fract() != 0.0 => {
+            let raw = columns.value(row, column).unwrap_or("");
+            Err(unparsable(columns, line, column, raw, "应为非负整数".to_string()))
+        }
+        value => Ok(value),
+    }
+}
+
+// 分类字段: 缺失或不在词表中都视为无法解析
+fn parse_category(
+    vocab: &Vocabulary,
+    columns: &ColumnMap,
+    row: &[String],
+    line: usize,
+    column: Column,
+) -> Result<usize, DataError> {
+    let raw = columns.value(row, column).unwrap_or("");
+    match vocab.parse(raw) {
+        Ok(category) => category
+            .code()
+            .ok_or_else(|| unparsable(columns, line, column, raw, "未知类别".to_string())),
+        Err(e) => Err(unparsable(columns, line, column, raw, e.to_string())),
+    }
+}
+
+// 找不到文件时也尝试在当前目录下查找同名文件
+fn resolve_path(path: &str) -> Result<PathBuf, DataError> {
+    let path = Path::new(path);
+    if path.exists() {
+        return Ok(path.to_path_buf());
+    }
+
+    let file_name = path.file_name().unwrap_or_default().to_str().unwrap_or("titanic.csv");
+    let current_dir = std::env::current_dir()?;
+    let new_path = current_dir.join(file_name);
+
+    println!("也在查找: {}", new_path.display());
+
+    if new_path.exists() {
+        println!("在当前位置找到文件: {}", new_path.display());
+        return Ok(new_path);
+    }
+
+    // 列出当前目录下的文件帮助用户定位
+    println!("当前目录下的文件:");
+    if let Ok(entries) = std::fs::read_dir(&current_dir) {
+        for entry in entries.flatten() {
+            if let Ok(file_type) = entry.file_type() {
+                if file_type.is_file() {
+                    println!("  - {}", entry.file_name().to_string_lossy());
+                }
+            }
+        }
+    }
+    Err(DataError::FileNotFound(path.to_path_buf()))
+}
+
+// ---------------------------
+// CSV 读取和数据预处理
+// ---------------------------
+// 宽松模式加载，并打印发现的问题
+pub fn load_csv(path: &str) -> Result<DataSet, DataError> {
+    let (dataset, report) = load_csv_with(path, &LoadOptions::new(ParseMode::Lenient))?;
+    if !report.issues.is_empty() {
+        println!("发现 {} 个数据问题:", report.issues.len());
+        for issue in report.issues.iter().take(10) {
+            println!("  - {}", issue);
+        }
+        if report.issues.len() > 10 {
+            println!("  ... 另有 {} 个问题未显示", report.issues.len() - 10);
+        }
+    }
+    Ok(dataset)
+}
+
+pub fn load_csv_with(path: &str, options: &LoadOptions) -> Result<(DataSet, LoadReport), DataError> {
+    println!("尝试加载文件: {}", path);
+
+    let path = resolve_path(path)?;
+    let file = File::open(&path)?;
+    let mut reader = CsvReader::new(BufReader::new(file));
+    let mut report = LoadReport::new(options.mode);
+
+    // 根据标题行确定各列位置，而不是写死下标
+    let header = match reader.next() {
+        Some(record) => record?,
+        None => return Err(SchemaError::MissingHeader.into()),
+    };
+    println!("CSV标题行: {}", header.fields.join(","));
+    let columns = Schema::titanic().resolve(&header.fields)?;
+    let labeled = columns.contains(Column::Survived);
+    if !labeled {
+        println!("没有 survived 列，按无标签数据加载（仅用于预测）");
+        // 预测结果按乘客编号提交，不能自行编号
+        if !columns.contains(Column::PassengerId) {
+            return Err(SchemaError::MissingColumns(vec![Column::PassengerId]).into());
+        }
+    }
+    if !columns.ignored().is_empty() {
+        println!("忽略未使用的列: {}", columns.ignored().join(", "));
+    }
+
+    let mut raw_rows = Vec::new();
+
+    for (i, record) in reader.enumerate() {
+        let i = i + 1;
+        report.records_read += 1;
+
+        let record = match record {
+            Ok(record) => record,
+            Err(e) => {
+                report.reject(labeled, e.into())?;
+                continue;
+            }
+        };
+
+        let fields: Vec<String> = record.fields.iter().map(|s| s.trim().to_string()).collect();
+
+        // 调试输出前几行
+        if i <= 3 {
+            println!("第{}行数据: {:?}", i, fields);
+            println!("字段数量: {}", fields.len());
+        }
+
+        // 字段多了通常是姓名中的逗号没有加引号，后面的字段都错了位，和字段少一样不能使用
+        if fields.len() != columns.width() {
+            report.reject(labeled, DataError::MalformedRecord(CsvError {
+                line: record.line,
+                column: fields.len().min(columns.width()) + 1, // 第一个缺少或多出的字段
+                kind: CsvErrorKind::FieldCount {
+                    expected: columns.width(),
+                    found: fields.len(),
+                },
+            }))?;
+            continue;
+        }
+
+        raw_rows.push((record.line, fields));
+    }
+
+    let vocabularies = &options.vocabularies;
+
+    let raw_columns: Vec<Column> = Column::ALL.iter().copied().filter(|&c| columns.contains(c)).collect();
+
+    // 乘客表的各列
+    let mut passenger_ids = Vec::new();
+    let mut labels = Vec::new();
+    let mut pclasses = Vec::new();
+    let mut sexes = Vec::new();
+    let mut ages = Vec::new();
+    let mut fares = Vec::new();
+    let mut sibsps = Vec::new();
+    let mut parches = Vec::new();
+    let mut names = Vec::new();
+    let mut tickets = Vec::new();
+    let mut cabins = Vec::new();
+    let mut embarkeds = Vec::new();
+    let mut raw_values = Vec::new();
+    let mut rejected = Vec::new();
+
+    for (i, (line, row)) in raw_rows.iter().enumerate() {
+        let line = *line;
+        let values: Vec<String> = raw_columns
+            .iter()
+            .map(|&c| columns.get(row, c).unwrap_or("").to_string())
+            .collect();
+
+        // 乘客编号。精简格式的训练数据没有该列，按数据行顺序从1开始编号；
+        // 有该列时缺失或无法解析的编号不能用行号代替，否则可能与真实编号重复
+        let passenger_id = if columns.contains(Column::PassengerId) {
+            let id = match parse_number::<u32>(&columns, row, line, Column::PassengerId) {
+                Ok(Some(id)) => Ok(id),
+                Ok(None) => Err(unparsable(&columns, line, Column::PassengerId, "", "乘客编号缺失".to_string())),
+                Err(e) => Err(e),
+            };
+            match id {
+                Ok(id) => id,
+                Err(e) => {
+                    report.reject(labeled, e)?;
+                    rejected.push(values);
+                    continue;
+                }
+            }
+        } else {
+            i as u32 + 1
+        };
+
+        // 解析标签 (survived)，标签缺失或无法解析的记录不能用于训练
+        let survived = if labeled {
+            let raw = columns.value(row, Column::Survived);
+            match raw.map(str::parse::<f64>) {
+                Some(Ok(v)) if v == 0.0 || v == 1.0 => v,
+                _ => {
+                    let reason = if raw.is_none() { "标签缺失" } else { "标签只能是 0 或 1" };
+                    let raw = raw.unwrap_or("");
+                    report.skip(unparsable(&columns, line, Column::Survived, raw, reason.to_string()))?;
+                    rejected.push(values);
+                    continue;
+                }
+            }
+        } else {
+            0.0
+        };
+
+        // 解析特征: 舱位和性别按词表精确匹配，无法识别时训练数据跳过该记录，待预测数据报错
+        let pclass = match parse_category(&vocabularies.pclass, &columns, row, line, Column::Pclass) {
+            Ok(code) => code as u32,
+            Err(e) => {
+                report.reject(labeled, e)?;
+                rejected.push(values);
+                continue;
+            }
+        };
+
+        // 性别处理: male = 0, female = 1
+        let sex = match parse_category(&vocabularies.sex, &columns, row, line, Column::Sex) {
+            Ok(code) => code as u32,
+            Err(e) => {
+                report.reject(labeled, e)?;
+                rejected.push(values);
+                continue;
+            }
+        };
+
+        // 数值字段无法解析时按缺失处理，缺失值在训练时由 Pipeline 填充
+        let age = parse_number::<f64>(&columns, row, line, Column::Age).or_else(|e| report.note(e).map(|_| None))?;
+        let fare = parse_number::<f64>(&columns, row, line, Column::Fare).or_else(|e| report.note(e).map(|_| None))?;
+        let sibsp = parse_count(&columns, row, line, Column::SibSp).or_else(|e| report.note(e).map(|_| None))?;
+        let parch = parse_count(&columns, row, line, Column::Parch).or_else(|e| report.note(e).map(|_| None))?;
+
+        // 登船港口只用于分析，无法识别时记为空值，由数据质量报告统计
+        let embarked = columns
+            .value(row, Column::Embarked)
+            .and_then(|raw| vocabularies.embarked.parse(raw).ok())
+            .and_then(|category| category.code())
+            .map(|code| code as u32);
+
+        passenger_ids.push(passenger_id);
+        if labeled {
+            labels.push(survived);
+        }
+        pclasses.push(Some(pclass));
+        sexes.push(Some(sex));
+        ages.push(age);
+        fares.push(fare);
+        sibsps.push(sibsp);
+        parches.push(parch);
+        names.push(columns.value(row, Column::Name).map(str::to_string));
+        tickets.push(columns.value(row, Column::Ticket).map(str::to_string));
+        cabins.push(columns.value(row, Column::Cabin).map(str::to_string));
+        embarkeds.push(embarked);
+        raw_values.push(values);
+
+        // 显示前几条处理后的数据用于调试
+        if i < 3 {
+            println!("处理后的第{}条数据: id={}, pclass={}, sex={}, age={:?}, fare={:?}, sibsp={:?}, parch={:?}, survived={}",
+                     i, passenger_id, pclass + 1, sex, age, fare, sibsp, parch, survived);
+        }
+    }
+
+    // 组装乘客表，只包含文件中存在的可选列
+    let dictionary = |vocab: &Vocabulary| vocab.labels().to_vec();
+    let mut frame = DataFrame::new(vec![Series::from_i64(
+        Column::PassengerId.name(),
+        passenger_ids.iter().map(|&id| Some(id as i64)).collect(),
+    )])?;
+    if labeled {
+        frame.add_column(Series::from_bool(Column::Survived.name(), labels.iter().map(|&y| Some(y == 1.0)).collect()))?;
+    }
+    frame.add_column(Series::from_codes(Column::Pclass.name(), pclasses, dictionary(&vocabularies.pclass)))?;
+    if columns.contains(Column::Name) {
+        frame.add_column(Series::from_strings(Column::Name.name(), names))?;
+    }
+    frame.add_column(Series::from_codes(Column::Sex.name(), sexes, dictionary(&vocabularies.sex)))?;
+    frame.add_column(Series::from_f64(Column::Age.name(), ages))?;
+    frame.add_column(Series::from_f64(Column::SibSp.name(), sibsps))?;
+    frame.add_column(Series::from_f64(Column::Parch.name(), parches))?;
+    if columns.contains(Column::Ticket) {
+        frame.add_column(Series::from_strings(Column::Ticket.name(), tickets))?;
+    }
+    frame.add_column(Series::from_f64(Column::Fare.name(), fares))?;
+    if columns.contains(Column::Cabin) {
+        frame.add_column(Series::from_strings(Column::Cabin.name(), cabins))?;
+    }
+    if columns.contains(Column::Embarked) {
+        frame.add_column(Series::from_codes(Column::Embarked.name(), embarkeds, dictionary(&vocabularies.embarked)))?;
+    }
+    // 从姓名派生称谓、姓氏等分类列
+    if columns.contains(Column::Name) {
+        add_name_features(&mut frame)?;
+    }
+
+    let missing_age = frame.column(Column::Age.name())?.null_count();
+    if missing_age > 0 {
+        println!("{} 条记录缺少年龄，训练时用训练集的中位数填充", missing_age);
+    }
+    let features = feature_matrix(&frame)?;
+
+    println!("总共读取 {} 条记录，跳过 {} 条无效记录", report.records_read, report.records_skipped);
+    println!("成功处理 {} 条有效数据", features.rows());
+    let unreadable = report.records_skipped - rejected.len();
+    let raw = RawTable { columns: raw_columns, rows: raw_values, rejected, unreadable };
+    let feature_names = FEATURE_NAMES.iter().map(|s| s.to_string()).collect();
+    Ok((
+        DataSet {
+            passenger_ids,
+            features,
+            feature_names,
+            labels,
+            frame,
+            raw,
+            vocabularies: vocabularies.clone(),
+        },
+        report,
+    ))
+}
+
+// ---------------------------
+// 预测结果输出 (Kaggle 提交格式)
+// ---------------------------
+pub fn write_submission(path: &str, passenger_ids: &[u32], predictions: &[f64]) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(file, "PassengerId,Survived")?;
+    for (id, pred) in passenger_ids.iter().zip(predictions) {
+        writeln!(file, "{},{}", id, *pred as u8)?;
+    }
+    file.flush()
+}
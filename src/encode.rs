@@ -0,0 +1,439 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::frame::Series;
+use crate::matrix::Matrix;
+use crate::pipeline::Transformer;
+use crate::rng::Rng;
+use crate::split::{k_fold, leave_one_out};
+
+// ---------------------------
+// 分类特征编码
+// ---------------------------
+// 编码器处理一列分类取值（字符串，None 表示缺失），输出若干数值列。
+// 乘客表中的字符串列用 category_values 取值后直接编码；
+// 特征矩阵中已是数值编号的列（如 pclass）用 ColumnEncoder 放进流水线。
+pub trait CategoryEncoder {
+    fn fit(&mut self, values: &[Option<String>], y: &[f64]);
+
+    fn transform(&self, values: &[Option<String>]) -> Matrix;
+
+    fn fit_transform(&mut self, values: &[Option<String>], y: &[f64]) -> Matrix {
+        self.fit(values, y);
+        self.transform(values)
+    }
+
+    // 输出各列的名称，input 为被编码列的名称
+    fn feature_names_out(&self, input: &str) -> Vec<String>;
+}
+
+// 乘客表中一列的取值，数值按最短形式格式化（1.0 -> "1"）
+pub fn category_values(series: &Series) -> Vec<Option<String>> {
+    (0..series.len())
+        .map(|i| {
+            let value = series.get(i);
+            if value.is_null() {
+                None
+            } else {
+                Some(value.to_string())
+            }
+        })
+        .collect()
+}
+
+// 类别排序: 都是数字时按数值，否则按字符串；缺失排在最后，
+// 这样 one-hot 的 drop_first 去掉的参照类别总是一个真实取值，缺失值仍有自己的列
+fn compare_categories(a: &Option<String>, b: &Option<String>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.total_cmp(&y),
+            _ => a.cmp(b),
+        },
+    }
+}
+
+// 训练数据中出现过的类别，排序去重
+fn distinct_categories(values: &[Option<String>]) -> Vec<Option<String>> {
+    let mut categories = values.to_vec();
+    categories.sort_by(compare_categories);
+    categories.dedup();
+    categories
+}
+
+fn category_label(category: &Option<String>) -> &str {
+    category.as_deref().unwrap_or("null")
+}
+
+// ---------------------------
+// One-hot 编码
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unknown {
+    Ignore,   // 训练时没见过的类别编码为全 0
+    Separate, // 额外增加一列 "<列名>=<unknown>"
+}
+
+#[derive(Debug, Clone)]
+pub struct OneHotEncoder {
+    drop_first: bool,
+    unknown: Unknown,
+    categories: Vec<Option<String>>,
+}
+
+impl Default for OneHotEncoder {
+    fn default() -> Self {
+        OneHotEncoder::new()
+    }
+}
+
+impl OneHotEncoder {
+    pub fn new() -> Self {
+        OneHotEncoder {
+            drop_first: false,
+            unknown: Unknown::Ignore,
+            categories: Vec::new(),
+        }
+    }
+
+    // 去掉第一个类别作为参照，避免线性模型中的完全共线
+    pub fn drop_first(mut self) -> Self {
+        self.drop_first = true;
+        self
+    }
+
+    pub fn handle_unknown(mut self, unknown: Unknown) -> Self {
+        self.unknown = unknown;
+        self
+    }
+
+    pub fn categories(&self) -> &[Option<String>] {
+        &self.categories
+    }
+
+    // 实际输出的类别（drop_first 时去掉第一个）
+    fn kept(&self) -> &[Option<String>] {
+        if self.drop_first && !self.categories.is_empty() {
+            &self.categories[1..]
+        } else {
+            &self.categories
+        }
+    }
+
+    fn width(&self) -> usize {
+        self.kept().len() + usize::from(self.unknown == Unknown::Separate)
+    }
+}
+
+impl CategoryEncoder for OneHotEncoder {
+    fn fit(&mut self, values: &[Option<String>], _y: &[f64]) {
+        self.categories = distinct_categories(values);
+    }
+
+    fn transform(&self, values: &[Option<String>]) -> Matrix {
+        let width = self.width();
+        let mut out = Matrix::zeros(values.len(), width);
+        for (i, value) in values.iter().enumerate() {
+            match self.categories.iter().position(|c| c == value) {
+                Some(0) if self.drop_first => {}
+                Some(code) => out[(i, code - usize::from(self.drop_first))] = 1.0,
+                None if self.unknown == Unknown::Separate => out[(i, width - 1)] = 1.0,
+                None => {}
+            }
+        }
+        out
+    }
+
+    fn feature_names_out(&self, input: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .kept()
+            .iter()
+            .map(|c| format!("{}={}", input, category_label(c)))
+            .collect();
+        if self.unknown == Unknown::Separate {
+            names.push(format!("{}=<unknown>", input));
+        }
+        names
+    }
+}
+
+// ---------------------------
+// 序号编码
+// ---------------------------
+// 类别映射为 0, 1, 2, ...；默认按类别排序，也可以显式给出顺序。
+// 训练时没见过的类别编码为 NaN，交给后面的填充步骤处理。
+#[derive(Debug, Clone, Default)]
+pub struct OrdinalEncoder {
+    order: Option<Vec<String>>,
+    categories: Vec<Option<String>>,
+}
+
+impl OrdinalEncoder {
+    pub fn new() -> Self {
+        OrdinalEncoder::default()
+    }
+
+    // 显式顺序，例如舱位 ["3", "2", "1"]；不在列表中的取值视为未知
+    pub fn with_order(mut self, order: &[&str]) -> Self {
+        self.order = Some(order.iter().map(|s| s.to_string()).collect());
+        self
+    }
+
+    pub fn categories(&self) -> &[Option<String>] {
+        &self.categories
+    }
+}
+
+impl CategoryEncoder for OrdinalEncoder {
+    fn fit(&mut self, values: &[Option<String>], _y: &[f64]) {
+        self.categories = match &self.order {
+            Some(order) => order.iter().cloned().map(Some).collect(),
+            None => distinct_categories(values).into_iter().filter(Option::is_some).collect(),
+        };
+    }
+
+    fn transform(&self, values: &[Option<String>]) -> Matrix {
+        let data = values
+            .iter()
+            .map(|value| match value {
+                Some(_) => self.categories.iter().position(|c| c == value).map_or(f64::NAN, |code| code as f64),
+                None => f64::NAN,
+            })
+            .collect();
+        Matrix::from_vec(values.len(), 1, data).expect("单列矩阵")
+    }
+
+    fn feature_names_out(&self, input: &str) -> Vec<String> {
+        vec![input.to_string()]
+    }
+}
+
+// ---------------------------
+// 目标编码
+// ---------------------------
+// 每个类别编码为平滑后的生还率: (类别内生还数 + m × 总体生还率) / (类别样本数 + m)。
+// 在训练数据上 fit_transform 时按 K 折交叉编码（每行只用其它折的统计量，样本数少于 K 时留一），
+// 避免把自己的标签泄漏进特征；transform 使用全部训练数据的统计量，未知类别取总体生还率。
+#[derive(Debug, Clone)]
+pub struct TargetEncoder {
+    smoothing: f64,
+    folds: usize,
+    seed: u64,
+    prior: f64,
+    stats: HashMap<Option<String>, (f64, f64)>, // 类别 -> (生还数, 样本数)
+}
+
+impl Default for TargetEncoder {
+    fn default() -> Self {
+        TargetEncoder::new()
+    }
+}
+
+impl TargetEncoder {
+    pub fn new() -> Self {
+        TargetEncoder {
+            smoothing: 10.0,
+            folds: 5,
+            seed: 42,
+            prior: 0.0,
+            stats: HashMap::new(),
+        }
+    }
+
+    // 平滑强度 m，越大越接近总体生还率
+    pub fn smoothing(mut self, m: f64) -> Self {
+        self.smoothing = m;
+        self
+    }
+
+    pub fn folds(mut self, k: usize) -> Self {
+        self.folds = k;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    fn statistics(values: &[Option<String>], y: &[f64], rows: &[usize]) -> (f64, HashMap<Option<String>, (f64, f64)>) {
+        let mut stats: HashMap<Option<String>, (f64, f64)> = HashMap::new();
+        let mut total = 0.0;
+        for &i in rows {
+            let entry = stats.entry(values[i].clone()).or_default();
+            entry.0 += y[i];
+            entry.1 += 1.0;
+            total += y[i];
+        }
+        let prior = if rows.is_empty() { 0.0 } else { total / rows.len() as f64 };
+        (prior, stats)
+    }
+
+    fn encode(&self, prior: f64, stats: &HashMap<Option<String>, (f64, f64)>, value: &Option<String>) -> f64 {
+        match stats.get(value) {
+            Some(&(positives, count)) => (positives + self.smoothing * prior) / (count + self.smoothing),
+            None => prior,
+        }
+    }
+}
+
+impl CategoryEncoder for TargetEncoder {
+    fn fit(&mut self, values: &[Option<String>], y: &[f64]) {
+        assert_eq!(values.len(), y.len(), "目标编码需要与取值一一对应的标签");
+        let rows: Vec<usize> = (0..values.len()).collect();
+        (self.prior, self.stats) = TargetEncoder::statistics(values, y, &rows);
+    }
+
+    fn transform(&self, values: &[Option<String>]) -> Matrix {
+        let data = values.iter().map(|v| self.encode(self.prior, &self.stats, v)).collect();
+        Matrix::from_vec(values.len(), 1, data).expect("单列矩阵")
+    }
+
+    fn fit_transform(&mut self, values: &[Option<String>], y: &[f64]) -> Matrix {
+        self.fit(values, y);
+        let n = values.len();
+        // 样本数不足 K 折时改用留一编码，仍然保证每行不用到自己的标签
+        let folds = if n < self.folds.max(2) {
+            leave_one_out(n)
+        } else {
+            k_fold(n, self.folds.max(2), Some(&mut Rng::new(self.seed)))
+        };
+        let mut data = vec![0.0; n];
+        for fold in folds {
+            let (prior, stats) = TargetEncoder::statistics(values, y, &fold.train);
+            for &i in &fold.test {
+                data[i] = self.encode(prior, &stats, &values[i]);
+            }
+        }
+        Matrix::from_vec(n, 1, data).expect("单列矩阵")
+    }
+
+    fn feature_names_out(&self, input: &str) -> Vec<String> {
+        vec![format!("{}_target", input)]
+    }
+}
+
+// ---------------------------
+// 哈希编码
+// ---------------------------
+// 不需要学习词表，适合姓氏、船票号这类取值很多的列。类别按 FNV-1a 哈希分到 n 个桶，
+// 再用哈希的另一位决定 +1 / -1，使冲突的类别在期望上相互抵消。缺失值编码为全 0。
+#[derive(Debug, Clone, Copy)]
+pub struct HashingEncoder {
+    n_buckets: usize,
+}
+
+fn fnv1a(text: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+impl HashingEncoder {
+    pub fn new(n_buckets: usize) -> Self {
+        assert!(n_buckets > 0, "哈希桶数必须大于 0");
+        HashingEncoder { n_buckets }
+    }
+}
+
+impl CategoryEncoder for HashingEncoder {
+    fn fit(&mut self, _values: &[Option<String>], _y: &[f64]) {}
+
+    fn transform(&self, values: &[Option<String>]) -> Matrix {
+        let mut out = Matrix::zeros(values.len(), self.n_buckets);
+        for (i, value) in values.iter().enumerate() {
+            if let Some(value) = value {
+                let hash = fnv1a(value);
+                let bucket = (hash % self.n_buckets as u64) as usize;
+                out[(i, bucket)] += if hash >> 63 == 0 { 1.0 } else { -1.0 };
+            }
+        }
+        out
+    }
+
+    fn feature_names_out(&self, input: &str) -> Vec<String> {
+        (0..self.n_buckets).map(|b| format!("{}_hash{}", input, b)).collect()
+    }
+}
+
+// ---------------------------
+// 在流水线中编码特征矩阵的一列
+// ---------------------------
+// 把第 column 列（数值编号，NaN 为缺失）替换为编码后的若干列，其它列的顺序不变。
+// 编号本身有意义时（如 pclass 的 1、2、3）直接用编号作类别；字典编号（如 title）用 labels 换回标签，
+// 输出列名为 title=Mr 而不是 title=0
+pub struct ColumnEncoder<E: CategoryEncoder> {
+    column: usize,
+    encoder: E,
+    labels: Option<Vec<String>>,
+    width: usize,
+}
+
+impl<E: CategoryEncoder> ColumnEncoder<E> {
+    pub fn new(column: usize, encoder: E) -> Self {
+        ColumnEncoder { column, encoder, labels: None, width: 0 }
+    }
+
+    // 第 k 个标签对应编号 k；超出字典的编号按数值本身作类别
+    pub fn labels(mut self, labels: &[String]) -> Self {
+        self.labels = Some(labels.to_vec());
+        self
+    }
+
+    fn values(&self, x: &Matrix) -> Vec<Option<String>> {
+        let label = |v: f64| {
+            let labels = self.labels.as_deref()?;
+            if v >= 0.0 && v.fract() == 0.0 { labels.get(v as usize).cloned() } else { None }
+        };
+        x.column(self.column)
+            .iter()
+            .map(|v| if v.is_nan() { None } else { Some(label(v).unwrap_or_else(|| v.to_string())) })
+            .collect()
+    }
+
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    fn splice(&self, x: &Matrix, encoded: Matrix) -> Matrix {
+        let cols = x.cols() - 1 + encoded.cols();
+        let mut data = Vec::with_capacity(x.rows() * cols);
+        for (row, block) in x.iter_rows().zip(encoded.iter_rows()) {
+            data.extend_from_slice(&row[..self.column]);
+            data.extend_from_slice(block);
+            data.extend_from_slice(&row[self.column + 1..]);
+        }
+        Matrix::from_vec(x.rows(), cols, data).expect("编码后的形状由构造保证")
+    }
+}
+
+impl<E: CategoryEncoder> Transformer for ColumnEncoder<E> {
+    fn fit(&mut self, x: &Matrix, y: &[f64]) {
+        self.encoder.fit(&self.values(x), y);
+        self.width = x.cols();
+    }
+
+    fn transform(&self, x: &Matrix) -> Matrix {
+        assert_eq!(x.cols(), self.width, "ColumnEncoder 未 fit 或特征数不一致");
+        self.splice(x, self.encoder.transform(&self.values(x)))
+    }
+
+    // 目标编码需要在训练数据上交叉编码
+    fn fit_transform(&mut self, x: &Matrix, y: &[f64]) -> Matrix {
+        let encoded = self.encoder.fit_transform(&self.values(x), y);
+        self.width = x.cols();
+        self.splice(x, encoded)
+    }
+
+    fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        let mut names = input[..self.column].to_vec();
+        names.extend(self.encoder.feature_names_out(&input[self.column]));
+        names.extend_from_slice(&input[self.column + 1..]);
+        names
+    }
+}
@@ -0,0 +1,80 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::csv::CsvError;
+use crate::frame::FrameError;
+use crate::schema::SchemaError;
+
+// ---------------------------
+// 数据加载错误
+// ---------------------------
+#[derive(Debug)]
+pub enum DataError {
+    FileNotFound(PathBuf),
+    IoError(io::Error),
+    MalformedRecord(CsvError),
+    UnparsableField {
+        line: usize,
+        column: usize, // 字段序号（从1开始）
+        field: String, // 列名
+        value: String, // 原始取值
+        reason: String,
+    },
+    SchemaMismatch(SchemaError),
+    Frame(FrameError), // 组装乘客表或生成特征失败
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataError::FileNotFound(path) => write!(f, "文件不存在: {}", path.display()),
+            DataError::IoError(e) => write!(f, "读取文件失败: {}", e),
+            DataError::MalformedRecord(e) => write!(f, "{}", e),
+            DataError::UnparsableField { line, column, field, value, reason } => write!(
+                f,
+                "无法解析字段 {} (第 {} 行, 第 {} 列) 的值 '{}': {}",
+                field, line, column, value, reason
+            ),
+            DataError::SchemaMismatch(e) => write!(f, "CSV结构不匹配: {}", e),
+            DataError::Frame(e) => write!(f, "数据表错误: {}", e),
+        }
+    }
+}
+
+impl Error for DataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DataError::IoError(e) => Some(e),
+            DataError::MalformedRecord(e) => Some(e),
+            DataError::SchemaMismatch(e) => Some(e),
+            DataError::Frame(e) => Some(e),
+            DataError::FileNotFound(_) | DataError::UnparsableField { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for DataError {
+    fn from(e: io::Error) -> Self {
+        DataError::IoError(e)
+    }
+}
+
+impl From<CsvError> for DataError {
+    fn from(e: CsvError) -> Self {
+        DataError::MalformedRecord(e)
+    }
+}
+
+impl From<SchemaError> for DataError {
+    fn from(e: SchemaError) -> Self {
+        DataError::SchemaMismatch(e)
+    }
+}
+
+impl From<FrameError> for DataError {
+    fn from(e: FrameError) -> Self {
+        DataError::Frame(e)
+    }
+}
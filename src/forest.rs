@@ -0,0 +1,145 @@
+use crate::matrix::Matrix;
+use crate::model::default_feature_names;
+use crate::rng::Rng;
+use crate::tree::{DecisionTree, MaxFeatures, TreeParams};
+
+// ---------------------------
+// 随机森林
+// ---------------------------
+// 每棵树在 bootstrap 抽样的数据上训练，分裂时只看随机的一部分特征，
+// 各树的随机种子都来自同一个以 seed 初始化的生成器，结果可复现
+pub struct RandomForest {
+    n_trees: usize,
+    params: TreeParams,
+    bootstrap: bool,
+    seed: u64,
+    trees: Vec<DecisionTree>,
+    n_features: usize,
+    feature_names: Vec<String>,
+}
+
+impl RandomForest {
+    pub fn new(n_trees: usize) -> Self {
+        RandomForest {
+            n_trees,
+            params: TreeParams {
+                max_features: MaxFeatures::Sqrt,
+                ..TreeParams::default()
+            },
+            bootstrap: true,
+            seed: 42,
+            trees: Vec::new(),
+            n_features: 0,
+            feature_names: Vec::new(),
+        }
+    }
+
+    // 每棵树的生长参数，包括 max_features
+    pub fn tree_params(mut self, params: TreeParams) -> Self {
+        self.params = params;
+        self
+    }
+
+    // 默认 Sqrt
+    pub fn max_features(mut self, max_features: MaxFeatures) -> Self {
+        self.params.max_features = max_features;
+        self
+    }
+
+    // 关闭后每棵树都使用完整的训练数据，只靠特征抽样产生差异
+    pub fn bootstrap(mut self, bootstrap: bool) -> Self {
+        self.bootstrap = bootstrap;
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn set_feature_names(&mut self, names: &[String]) {
+        self.feature_names = names.to_vec();
+    }
+
+    pub fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    // 训练数据的特征数，训练前为 0
+    pub fn n_features(&self) -> usize {
+        self.n_features
+    }
+
+    pub fn trees(&self) -> &[DecisionTree] {
+        &self.trees
+    }
+
+    // 各棵树特征重要性（归一化的不纯度下降）的平均，按重要性从大到小排列
+    pub fn feature_importances(&self) -> Vec<(String, f64)> {
+        let mut totals = vec![0.0; self.feature_names.len()];
+        for tree in &self.trees {
+            for (total, importance) in totals.iter_mut().zip(tree.feature_importances()) {
+                *total += importance;
+            }
+        }
+        let sum: f64 = totals.iter().sum();
+        let mut importances: Vec<(String, f64)> = self
+            .feature_names
+            .iter()
+            .cloned()
+            .zip(totals.into_iter().map(|t| if sum > 0.0 { t / sum } else { 0.0 }))
+            .collect();
+        importances.sort_by(|a, b| b.1.total_cmp(&a.1));
+        importances
+    }
+
+    pub fn fit(&mut self, x: &Matrix, y: &[f64]) {
+        assert_eq!(x.rows(), y.len(), "样本数 {} 与标签数 {} 不一致", x.rows(), y.len());
+        // 先丢掉上一次训练的树，训练数据为空时也不会继续用旧模型预测
+        self.trees.clear();
+        if x.is_empty() {
+            println!("警告: 训练数据为空，跳过随机森林训练");
+            return;
+        }
+        
+        println!("开始训练随机森林，数据量: {}", x.rows());
+        self.n_features = x.cols();
+        if self.feature_names.len() != x.cols() {
+            self.feature_names = default_feature_names(x.cols());
+        }
+        
+        let mut rng = Rng::new(self.seed);
+        for i in 0..self.n_trees {
+            let mut tree_rng = Rng::new(rng.next_u64());
+            let tree = if self.bootstrap {
+                let rows = tree_rng.sample_with_replacement(x.rows(), x.rows());
+                let sample_y: Vec<f64> = rows.iter().map(|&r| y[r]).collect();
+                DecisionTree::train_with(&x.select_rows(&rows), &sample_y, &self.params, &mut tree_rng)
+            } else {
+                DecisionTree::train_with(x, y, &self.params, &mut tree_rng)
+            };
+            self.trees.push(tree);
+            if (i + 1) % 5 == 0 {
+                println!("已训练 {} 棵树", i + 1);
+            }
+        }
+    }
+
+    pub fn predict(&self, x: &Matrix) -> Vec<f64> {
+        self.predict_proba(x)
+            .into_iter()
+            .map(|p| if p >= 0.5 { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    // 各棵树叶子上正类比例的平均
+    pub fn predict_proba(&self, x: &Matrix) -> Vec<f64> {
+        if x.is_empty() || self.trees.is_empty() {
+            return vec![0.0; x.rows()];
+        }
+
+        x.iter_rows()
+            .map(|xi| self.trees.iter().map(|tree| tree.predict_proba(xi)).sum::<f64>() / self.trees.len() as f64)
+            .collect()
+    }
+}
@@ -0,0 +1,777 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+// ---------------------------
+// 空值位图
+// ---------------------------
+// 每个值一位，1 表示有效，0 表示空值
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Bitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bitmap {
+    pub fn new() -> Self {
+        Bitmap::default()
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        if bit {
+            self.words[self.len / 64] |= 1 << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "位图下标越界: {} >= {}", i, self.len);
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // 置位（有效值）的个数
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+}
+
+impl FromIterator<bool> for Bitmap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bitmap = Bitmap::new();
+        for bit in iter {
+            bitmap.push(bit);
+        }
+        bitmap
+    }
+}
+
+// ---------------------------
+// 列类型与取值
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataType {
+    Float,
+    Int,
+    Categorical,
+    Str,
+    Bool,
+}
+
+impl fmt::Display for DataType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            DataType::Float => "f64",
+            DataType::Int => "i64",
+            DataType::Categorical => "categorical",
+            DataType::Str => "str",
+            DataType::Bool => "bool",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// 列存储。空值位置上保存占位值（0、空串、false、编号 0），以位图为准。
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnData {
+    Float(Vec<f64>),
+    Int(Vec<i64>),
+    Categorical { codes: Vec<u32>, dictionary: Vec<String> }, // 值是 dictionary 的下标
+    Str(Vec<String>),
+    Bool(Vec<bool>),
+}
+
+impl ColumnData {
+    pub fn dtype(&self) -> DataType {
+        match self {
+            ColumnData::Float(_) => DataType::Float,
+            ColumnData::Int(_) => DataType::Int,
+            ColumnData::Categorical { .. } => DataType::Categorical,
+            ColumnData::Str(_) => DataType::Str,
+            ColumnData::Bool(_) => DataType::Bool,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            ColumnData::Float(v) => v.len(),
+            ColumnData::Int(v) => v.len(),
+            ColumnData::Categorical { codes, .. } => codes.len(),
+            ColumnData::Str(v) => v.len(),
+            ColumnData::Bool(v) => v.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// 单元格的值，分类列返回对应的标签
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value<'a> {
+    Null,
+    Float(f64),
+    Int(i64),
+    Str(&'a str),
+    Bool(bool),
+}
+
+impl Value<'_> {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    // 数值视图: 整数和布尔值转换为 f64，字符串和空值返回 None
+    pub fn as_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Float(v) => Some(v),
+            Value::Int(v) => Some(v as f64),
+            Value::Bool(v) => Some(if v { 1.0 } else { 0.0 }),
+            Value::Str(_) | Value::Null => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Float(v) => write!(f, "{}", v),
+            Value::Int(v) => write!(f, "{}", v),
+            Value::Str(s) => write!(f, "{}", s),
+            Value::Bool(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+// 分组和连接用的可哈希键。浮点数按位比较（-0.0 归一为 0.0）。
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Key {
+    Null,
+    Float(u64),
+    Int(i64),
+    Str(String),
+    Bool(bool),
+}
+
+impl From<Value<'_>> for Key {
+    fn from(value: Value<'_>) -> Self {
+        match value {
+            Value::Null => Key::Null,
+            Value::Float(v) => Key::Float(if v == 0.0 { 0.0f64.to_bits() } else { v.to_bits() }),
+            Value::Int(v) => Key::Int(v),
+            Value::Str(s) => Key::Str(s.to_string()),
+            Value::Bool(v) => Key::Bool(v),
+        }
+    }
+}
+
+// ---------------------------
+// 错误
+// ---------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameError {
+    ColumnNotFound(String),
+    DuplicateColumn(String),
+    LengthMismatch { column: String, expected: usize, found: usize },
+    TypeMismatch { column: String, expected: DataType, found: DataType },
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::ColumnNotFound(name) => write!(f, "没有名为 '{}' 的列", name),
+            FrameError::DuplicateColumn(name) => write!(f, "列 '{}' 重复", name),
+            FrameError::LengthMismatch { column, expected, found } => {
+                write!(f, "列 '{}' 的长度为 {}，应为 {}", column, found, expected)
+            }
+            FrameError::TypeMismatch { column, expected, found } => {
+                write!(f, "列 '{}' 的类型为 {}，需要 {}", column, found, expected)
+            }
+        }
+    }
+}
+
+impl Error for FrameError {}
+
+// ---------------------------
+// 列 (Series)
+// ---------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct Series {
+    name: String,
+    data: ColumnData,
+    validity: Bitmap,
+}
+
+impl Series {
+    // 所有值都有效
+    pub fn new(name: &str, data: ColumnData) -> Self {
+        let validity = (0..data.len()).map(|_| true).collect();
+        Series { name: name.to_string(), data, validity }
+    }
+
+    pub fn from_f64(name: &str, values: Vec<Option<f64>>) -> Self {
+        let validity = values.iter().map(Option::is_some).collect();
+        let data = ColumnData::Float(values.into_iter().map(|v| v.unwrap_or(0.0)).collect());
+        Series { name: name.to_string(), data, validity }
+    }
+
+    pub fn from_i64(name: &str, values: Vec<Option<i64>>) -> Self {
+        let validity = values.iter().map(Option::is_some).collect();
+        let data = ColumnData::Int(values.into_iter().map(|v| v.unwrap_or(0)).collect());
+        Series { name: name.to_string(), data, validity }
+    }
+
+    pub fn from_bool(name: &str, values: Vec<Option<bool>>) -> Self {
+        let validity = values.iter().map(Option::is_some).collect();
+        let data = ColumnData::Bool(values.into_iter().map(|v| v.unwrap_or(false)).collect());
+        Series { name: name.to_string(), data, validity }
+    }
+
+    pub fn from_strings(name: &str, values: Vec<Option<String>>) -> Self {
+        let validity = values.iter().map(Option::is_some).collect();
+        let data = ColumnData::Str(values.into_iter().map(Option::unwrap_or_default).collect());
+        Series { name: name.to_string(), data, validity }
+    }
+
+    // 分类列，字典按首次出现的顺序建立
+    pub fn categorical(name: &str, values: &[Option<&str>]) -> Self {
+        let mut dictionary: Vec<String> = Vec::new();
+        let mut lookup: HashMap<&str, u32> = HashMap::new();
+        let codes = values
+            .iter()
+            .map(|v| {
+                v.map(|label| {
+                    *lookup.entry(label).or_insert_with(|| {
+                        dictionary.push(label.to_string());
+                        dictionary.len() as u32 - 1
+                    })
+                })
+            })
+            .collect();
+        Series::from_codes(name, codes, dictionary)
+    }
+
+    // 用给定的字典（如 Vocabulary 的规范取值）建立分类列
+    pub fn from_codes(name: &str, codes: Vec<Option<u32>>, dictionary: Vec<String>) -> Self {
+        assert!(
+            codes.iter().flatten().all(|&c| (c as usize) < dictionary.len()),
+            "分类列 '{}' 的编号超出字典范围",
+            name
+        );
+        let validity = codes.iter().map(Option::is_some).collect();
+        let codes = codes.into_iter().map(|c| c.unwrap_or(0)).collect();
+        Series { name: name.to_string(), data: ColumnData::Categorical { codes, dictionary }, validity }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rename(mut self, name: &str) -> Self {
+        self.name = name.to_string();
+        self
+    }
+
+    pub fn data(&self) -> &ColumnData {
+        &self.data
+    }
+
+    pub fn dtype(&self) -> DataType {
+        self.data.dtype()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn is_null(&self, row: usize) -> bool {
+        !self.validity.get(row)
+    }
+
+    pub fn null_count(&self) -> usize {
+        self.len() - self.validity.count_ones()
+    }
+
+    pub fn get(&self, row: usize) -> Value<'_> {
+        if self.is_null(row) {
+            return Value::Null;
+        }
+        match &self.data {
+            ColumnData::Float(v) => Value::Float(v[row]),
+            ColumnData::Int(v) => Value::Int(v[row]),
+            ColumnData::Categorical { codes, dictionary } => Value::Str(&dictionary[codes[row] as usize]),
+            ColumnData::Str(v) => Value::Str(&v[row]),
+            ColumnData::Bool(v) => Value::Bool(v[row]),
+        }
+    }
+
+    pub fn f64_at(&self, row: usize) -> Option<f64> {
+        self.get(row).as_f64()
+    }
+
+    pub fn str_at(&self, row: usize) -> Option<&str> {
+        match self.get(row) {
+            Value::Str(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    // 分类列的编号，其它类型或空值返回 None
+    pub fn code_at(&self, row: usize) -> Option<u32> {
+        match &self.data {
+            ColumnData::Categorical { codes, .. } if !self.is_null(row) => Some(codes[row]),
+            _ => None,
+        }
+    }
+
+    pub fn dictionary(&self) -> Option<&[String]> {
+        match &self.data {
+            ColumnData::Categorical { dictionary, .. } => Some(dictionary),
+            _ => None,
+        }
+    }
+
+    // 所有有效值的数值视图，非数值列返回 TypeMismatch
+    pub fn to_f64(&self) -> Result<Vec<Option<f64>>, FrameError> {
+        match self.dtype() {
+            DataType::Float | DataType::Int | DataType::Bool => Ok((0..self.len()).map(|i| self.f64_at(i)).collect()),
+            found => Err(FrameError::TypeMismatch {
+                column: self.name.clone(),
+                expected: DataType::Float,
+                found,
+            }),
+        }
+    }
+
+    pub fn take(&self, indices: &[usize]) -> Series {
+        let indices: Vec<Option<usize>> = indices.iter().map(|&i| Some(i)).collect();
+        self.take_opt(&indices)
+    }
+
+    // None 的位置填空值（左连接时右表没有匹配的行）
+    fn take_opt(&self, indices: &[Option<usize>]) -> Series {
+        fn pick<T: Clone>(values: &[T], indices: &[Option<usize>], default: T) -> Vec<T> {
+            indices.iter().map(|i| i.map_or_else(|| default.clone(), |i| values[i].clone())).collect()
+        }
+        let data = match &self.data {
+            ColumnData::Float(v) => ColumnData::Float(pick(v, indices, 0.0)),
+            ColumnData::Int(v) => ColumnData::Int(pick(v, indices, 0)),
+            ColumnData::Categorical { codes, dictionary } => ColumnData::Categorical {
+                codes: pick(codes, indices, 0),
+                dictionary: dictionary.clone(),
+            },
+            ColumnData::Str(v) => ColumnData::Str(pick(v, indices, String::new())),
+            ColumnData::Bool(v) => ColumnData::Bool(pick(v, indices, false)),
+        };
+        let validity = indices.iter().map(|i| i.is_some_and(|i| !self.is_null(i))).collect();
+        Series { name: self.name.clone(), data, validity }
+    }
+
+    // 比较两行的有效值；分类列按字典顺序（编号）比较
+    fn compare(&self, a: usize, b: usize) -> Ordering {
+        match &self.data {
+            ColumnData::Float(v) => v[a].total_cmp(&v[b]),
+            ColumnData::Int(v) => v[a].cmp(&v[b]),
+            ColumnData::Categorical { codes, .. } => codes[a].cmp(&codes[b]),
+            ColumnData::Str(v) => v[a].cmp(&v[b]),
+            ColumnData::Bool(v) => v[a].cmp(&v[b]),
+        }
+    }
+}
+
+// ---------------------------
+// 表 (DataFrame)
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinType {
+    Inner,
+    Left, // 保留左表所有行，没有匹配时右表的列为空值
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Agg {
+    Count, // 非空值个数
+    Sum,
+    Mean,
+    Min,
+    Max,
+}
+
+impl Agg {
+    pub fn name(self) -> &'static str {
+        match self {
+            Agg::Count => "count",
+            Agg::Sum => "sum",
+            Agg::Mean => "mean",
+            Agg::Min => "min",
+            Agg::Max => "max",
+        }
+    }
+}
+
+// 按列存储的表，所有列长度相同，列名唯一
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DataFrame {
+    columns: Vec<Series>,
+    height: usize,
+}
+
+impl DataFrame {
+    pub fn new(columns: Vec<Series>) -> Result<Self, FrameError> {
+        let mut frame = DataFrame {
+            height: columns.first().map_or(0, Series::len),
+            columns: Vec::with_capacity(columns.len()),
+        };
+        for series in columns {
+            frame.add_column(series)?;
+        }
+        Ok(frame)
+    }
+
+    pub fn add_column(&mut self, series: Series) -> Result<(), FrameError> {
+        if self.columns.is_empty() {
+            self.height = series.len();
+        }
+        if self.index_of(series.name()).is_some() {
+            return Err(FrameError::DuplicateColumn(series.name().to_string()));
+        }
+        if series.len() != self.height {
+            return Err(FrameError::LengthMismatch {
+                column: series.name().to_string(),
+                expected: self.height,
+                found: series.len(),
+            });
+        }
+        self.columns.push(series);
+        Ok(())
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.height == 0
+    }
+
+    pub fn column_names(&self) -> Vec<&str> {
+        self.columns.iter().map(Series::name).collect()
+    }
+
+    pub fn columns(&self) -> &[Series] {
+        &self.columns
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|s| s.name() == name)
+    }
+
+    pub fn has_column(&self, name: &str) -> bool {
+        self.index_of(name).is_some()
+    }
+
+    pub fn column(&self, name: &str) -> Result<&Series, FrameError> {
+        self.index_of(name)
+            .map(|j| &self.columns[j])
+            .ok_or_else(|| FrameError::ColumnNotFound(name.to_string()))
+    }
+
+    pub fn row(&self, i: usize) -> Vec<Value<'_>> {
+        self.columns.iter().map(|s| s.get(i)).collect()
+    }
+
+    // 按给定顺序选出若干列
+    pub fn select(&self, names: &[&str]) -> Result<DataFrame, FrameError> {
+        let columns = names
+            .iter()
+            .map(|name| self.column(name).cloned())
+            .collect::<Result<Vec<_>, _>>()?;
+        DataFrame::new(columns)
+    }
+
+    pub fn drop(&self, name: &str) -> Result<DataFrame, FrameError> {
+        let j = self.index_of(name).ok_or_else(|| FrameError::ColumnNotFound(name.to_string()))?;
+        let mut frame = self.clone();
+        frame.columns.remove(j);
+        Ok(frame)
+    }
+
+    // 按行下标取子表（顺序与 indices 一致，可以重复）
+    pub fn take(&self, indices: &[usize]) -> DataFrame {
+        DataFrame {
+            columns: self.columns.iter().map(|s| s.take(indices)).collect(),
+            height: indices.len(),
+        }
+    }
+
+    fn take_opt(&self, indices: &[Option<usize>]) -> DataFrame {
+        DataFrame {
+            columns: self.columns.iter().map(|s| s.take_opt(indices)).collect(),
+            height: indices.len(),
+        }
+    }
+
+    // 保留 mask 为 true 的行
+    pub fn filter(&self, mask: &[bool]) -> Result<DataFrame, FrameError> {
+        if mask.len() != self.height {
+            return Err(FrameError::LengthMismatch {
+                column: "mask".to_string(),
+                expected: self.height,
+                found: mask.len(),
+            });
+        }
+        let indices: Vec<usize> = (0..self.height).filter(|&i| mask[i]).collect();
+        Ok(self.take(&indices))
+    }
+
+    // 按某一列的值过滤，例如 frame.filter_by("age", |v| v.as_f64().is_some_and(|a| a < 18.0))
+    pub fn filter_by(&self, name: &str, predicate: impl Fn(Value<'_>) -> bool) -> Result<DataFrame, FrameError> {
+        let series = self.column(name)?;
+        let mask: Vec<bool> = (0..self.height).map(|i| predicate(series.get(i))).collect();
+        self.filter(&mask)
+    }
+
+    // 多列稳定排序，空值总是排在最后
+    pub fn sort_by(&self, keys: &[(&str, SortOrder)]) -> Result<DataFrame, FrameError> {
+        let keys = keys
+            .iter()
+            .map(|&(name, order)| self.column(name).map(|s| (s, order)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut indices: Vec<usize> = (0..self.height).collect();
+        indices.sort_by(|&a, &b| {
+            for &(series, order) in &keys {
+                let ordering = match (series.is_null(a), series.is_null(b)) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => match order {
+                        SortOrder::Ascending => series.compare(a, b),
+                        SortOrder::Descending => series.compare(b, a),
+                    },
+                };
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            Ordering::Equal
+        });
+        Ok(self.take(&indices))
+    }
+
+    fn row_keys(&self, names: &[&str]) -> Result<Vec<Vec<Key>>, FrameError> {
+        let series = names.iter().map(|name| self.column(name)).collect::<Result<Vec<_>, _>>()?;
+        Ok((0..self.height)
+            .map(|i| series.iter().map(|s| Key::from(s.get(i))).collect())
+            .collect())
+    }
+
+    // 按若干列分组，组按首次出现的顺序排列；空值自成一组
+    pub fn group_by(&self, keys: &[&str]) -> Result<GroupBy<'_>, FrameError> {
+        let mut lookup: HashMap<Vec<Key>, usize> = HashMap::new();
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for (i, key) in self.row_keys(keys)?.into_iter().enumerate() {
+            let g = *lookup.entry(key).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[g].push(i);
+        }
+        Ok(GroupBy {
+            frame: self,
+            keys: keys.iter().map(|s| s.to_string()).collect(),
+            groups,
+        })
+    }
+
+    // 按同名键列连接。结果为左表所有列加上右表的非键列，重名的列加 "_right" 后缀。
+    // 键为空值的行不与任何行匹配。
+    pub fn join(&self, other: &DataFrame, on: &[&str], how: JoinType) -> Result<DataFrame, FrameError> {
+        for name in on {
+            let (left, right) = (self.column(name)?, other.column(name)?);
+            if left.dtype() != right.dtype() {
+                return Err(FrameError::TypeMismatch {
+                    column: name.to_string(),
+                    expected: left.dtype(),
+                    found: right.dtype(),
+                });
+            }
+        }
+
+        let mut right_rows: HashMap<Vec<Key>, Vec<usize>> = HashMap::new();
+        for (j, key) in other.row_keys(on)?.into_iter().enumerate() {
+            if !key.contains(&Key::Null) {
+                right_rows.entry(key).or_default().push(j);
+            }
+        }
+
+        let mut left_indices = Vec::new();
+        let mut right_indices = Vec::new();
+        for (i, key) in self.row_keys(on)?.into_iter().enumerate() {
+            match right_rows.get(&key).filter(|_| !key.contains(&Key::Null)) {
+                Some(matches) => {
+                    for &j in matches {
+                        left_indices.push(i);
+                        right_indices.push(Some(j));
+                    }
+                }
+                None if how == JoinType::Left => {
+                    left_indices.push(i);
+                    right_indices.push(None);
+                }
+                None => {}
+            }
+        }
+
+        let mut joined = self.take(&left_indices);
+        let right = other.take_opt(&right_indices);
+        for series in right.columns {
+            if on.contains(&series.name()) {
+                continue;
+            }
+            let series = if joined.has_column(series.name()) {
+                let name = format!("{}_right", series.name());
+                series.rename(&name)
+            } else {
+                series
+            };
+            joined.add_column(series)?;
+        }
+        Ok(joined)
+    }
+}
+
+// 打印前 10 行
+impl fmt::Display for DataFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "DataFrame: {} 行 × {} 列", self.height, self.width())?;
+        let header: Vec<String> = self.columns.iter().map(|s| format!("{:>12}", s.name())).collect();
+        writeln!(f, "{}", header.join(" "))?;
+        let types: Vec<String> = self.columns.iter().map(|s| format!("{:>12}", s.dtype().to_string())).collect();
+        writeln!(f, "{}", types.join(" "))?;
+        for i in 0..self.height.min(10) {
+            let cells: Vec<String> = self
+                .row(i)
+                .iter()
+                .map(|v| {
+                    let text = v.to_string();
+                    let text: String = if text.chars().count() > 12 {
+                        text.chars().take(11).chain(std::iter::once('…')).collect()
+                    } else {
+                        text
+                    };
+                    format!("{:>12}", text)
+                })
+                .collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        if self.height > 10 {
+            writeln!(f, "... 另有 {} 行未显示", self.height - 10)?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------
+// 分组聚合
+// ---------------------------
+pub struct GroupBy<'a> {
+    frame: &'a DataFrame,
+    keys: Vec<String>,
+    groups: Vec<Vec<usize>>,
+}
+
+impl GroupBy<'_> {
+    // 每组的行下标
+    pub fn groups(&self) -> &[Vec<usize>] {
+        &self.groups
+    }
+
+    // 每组一行的键列
+    fn key_frame(&self) -> DataFrame {
+        let first: Vec<usize> = self.groups.iter().map(|rows| rows[0]).collect();
+        let names: Vec<&str> = self.keys.iter().map(String::as_str).collect();
+        self.frame.select(&names).expect("分组键列已检查").take(&first)
+    }
+
+    // 键列 + 每组行数 "count"；分组键中已有名为 count 的列时返回 DuplicateColumn
+    pub fn count(&self) -> Result<DataFrame, FrameError> {
+        let mut frame = self.key_frame();
+        let counts = self.groups.iter().map(|rows| Some(rows.len() as i64)).collect();
+        frame.add_column(Series::from_i64("count", counts))?;
+        Ok(frame)
+    }
+
+    // 键列 + 每个聚合一列，列名为 "<列名>_<聚合名>"。
+    // 除 Count 外只适用于数值列，忽略空值；全为空值的组结果为空值。
+    pub fn agg(&self, aggs: &[(&str, Agg)]) -> Result<DataFrame, FrameError> {
+        let mut frame = self.key_frame();
+        for &(name, agg) in aggs {
+            let series = self.frame.column(name)?;
+            let output = format!("{}_{}", name, agg.name());
+            if agg == Agg::Count {
+                let counts = self
+                    .groups
+                    .iter()
+                    .map(|rows| Some(rows.iter().filter(|&&i| !series.is_null(i)).count() as i64))
+                    .collect();
+                frame.add_column(Series::from_i64(&output, counts))?;
+                continue;
+            }
+            let values = series.to_f64()?;
+            let results = self
+                .groups
+                .iter()
+                .map(|rows| {
+                    let valid: Vec<f64> = rows.iter().filter_map(|&i| values[i]).collect();
+                    if valid.is_empty() {
+                        return None;
+                    }
+                    Some(match agg {
+                        Agg::Sum => valid.iter().sum(),
+                        Agg::Mean => valid.iter().sum::<f64>() / valid.len() as f64,
+                        Agg::Min => valid.iter().copied().fold(f64::INFINITY, f64::min),
+                        Agg::Max => valid.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+                        Agg::Count => unreachable!(),
+                    })
+                })
+                .collect();
+            frame.add_column(Series::from_f64(&output, results))?;
+        }
+        Ok(frame)
+    }
+}
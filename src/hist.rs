@@ -0,0 +1,147 @@
+use crate::matrix::Matrix;
+
+// ---------------------------
+// 直方图训练：特征分箱
+// ---------------------------
+// 每个特征按分位数切成至多 255 个箱，样本只保存 u8 的箱号；NaN 单独放在 MISSING_BIN。
+// 之后分裂只需要在箱的边界上比较，候选阈值数与样本数无关。
+pub const MAX_BINS: usize = 255;
+pub const MISSING_BIN: u8 = u8::MAX;
+const HISTOGRAM_SLOTS: usize = MISSING_BIN as usize + 1;
+
+#[derive(Debug, Clone)]
+pub struct BinnedMatrix {
+    codes: Vec<u8>,       // 按列存储，第 j 列第 i 行是 codes[j * rows + i]
+    edges: Vec<Vec<f64>>, // edges[j][b] 是第 j 列第 b 箱的上界：x <= edges[j][b] 等价于箱号 <= b
+    rows: usize,
+}
+
+impl BinnedMatrix {
+    // max_bins 包括所有非缺失的箱，取值 2..=255
+    pub fn new(x: &Matrix, max_bins: usize) -> Self {
+        assert!((2..=MAX_BINS).contains(&max_bins), "max_bins 应在 2..={} 之间: {}", MAX_BINS, max_bins);
+        let mut codes = Vec::with_capacity(x.rows() * x.cols());
+        let mut edges = Vec::with_capacity(x.cols());
+        for j in 0..x.cols() {
+            let column = x.column(j);
+            let mut values: Vec<f64> = column.iter().filter(|v| !v.is_nan()).collect();
+            let column_edges = bin_edges(&mut values, max_bins);
+            codes.extend(column.iter().map(|v| bin_code(&column_edges, v)));
+            edges.push(column_edges);
+        }
+        BinnedMatrix { codes, edges, rows: x.rows() }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.edges.len()
+    }
+
+    // 第 j 列所有样本的箱号
+    pub fn column(&self, j: usize) -> &[u8] {
+        &self.codes[j * self.rows..(j + 1) * self.rows]
+    }
+
+    pub fn edges(&self, j: usize) -> &[f64] {
+        &self.edges[j]
+    }
+
+    // 第 j 列非缺失箱的个数
+    pub fn n_bins(&self, j: usize) -> usize {
+        self.edges[j].len() + 1
+    }
+
+    // 与训练时相同的规则给新的值分箱
+    pub fn code(&self, j: usize, value: f64) -> u8 {
+        bin_code(&self.edges[j], value)
+    }
+}
+
+// 不同取值不超过 max_bins 个时每个取值一箱，否则按累计样本数等分；
+// 边界取相邻两个不同取值的中点，与精确分裂的阈值一致
+fn bin_edges(values: &mut [f64], max_bins: usize) -> Vec<f64> {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mut distinct: Vec<(f64, usize)> = Vec::new();
+    for &v in values.iter() {
+        match distinct.last_mut() {
+            Some((last, count)) if *last == v => *count += 1,
+            _ => distinct.push((v, 1)),
+        }
+    }
+
+    let midpoint = |a: f64, b: f64| {
+        let m = a + (b - a) / 2.0;
+        if m >= b { a } else { m }
+    };
+    let total = values.len() as f64;
+    let mut edges = Vec::new();
+    let mut cumulative = 0;
+    for pair in distinct.windows(2) {
+        let ((value, count), (next, _)) = (pair[0], pair[1]);
+        cumulative += count;
+        let target = total * (edges.len() + 1) as f64 / max_bins as f64;
+        if distinct.len() <= max_bins || cumulative as f64 >= target {
+            edges.push(midpoint(value, next));
+        }
+    }
+    edges
+}
+
+fn bin_code(edges: &[f64], value: f64) -> u8 {
+    if value.is_nan() {
+        MISSING_BIN
+    } else {
+        edges.partition_point(|&e| e < value) as u8
+    }
+}
+
+// ---------------------------
+// 节点直方图
+// ---------------------------
+// 每个特征每个箱上的样本数和标签之和（二分类的一阶统计量），
+// 子节点只需扫描较小的一个，较大的一个用父节点减去它得到
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Bin {
+    pub count: f64,
+    pub sum: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct Histogram {
+    bins: Vec<Bin>, // 每个特征 256 个槽，最后一个是 MISSING_BIN
+}
+
+impl Histogram {
+    // rows 可以重复（bootstrap 抽样），重复的行按次数计入
+    pub fn build(binned: &BinnedMatrix, y: &[f64], rows: &[usize]) -> Self {
+        let mut bins = vec![Bin::default(); binned.cols() * HISTOGRAM_SLOTS];
+        for (j, slots) in bins.chunks_mut(HISTOGRAM_SLOTS).enumerate() {
+            let codes = binned.column(j);
+            for &r in rows {
+                let bin = &mut slots[codes[r] as usize];
+                bin.count += 1.0;
+                bin.sum += y[r];
+            }
+        }
+        Histogram { bins }
+    }
+
+    // self - other，other 是 self 对应节点的一个子节点
+    pub fn subtract(&self, other: &Histogram) -> Histogram {
+        let bins = self
+            .bins
+            .iter()
+            .zip(&other.bins)
+            .map(|(a, b)| Bin { count: a.count - b.count, sum: a.sum - b.sum })
+            .collect();
+        Histogram { bins }
+    }
+
+    // 第 j 个特征的 256 个槽
+    pub fn feature(&self, j: usize) -> &[Bin] {
+        &self.bins[j * HISTOGRAM_SLOTS..(j + 1) * HISTOGRAM_SLOTS]
+    }
+}
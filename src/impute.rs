@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+
+use crate::matrix::Matrix;
+use crate::pipeline::Transformer;
+
+// ---------------------------
+// 缺失值填充
+// ---------------------------
+// 缺失值用 NaN 表示。所有填充器都只在 fit 的训练数据上学习填充值；
+// 训练数据中整列缺失、无从学习时填 0。
+fn observed(x: &Matrix, col: usize) -> Vec<f64> {
+    x.column(col).iter().filter(|v| !v.is_nan()).collect()
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(values.iter().sum::<f64>() / values.len() as f64)
+    }
+}
+
+fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        Some((values[mid - 1] + values[mid]) / 2.0)
+    } else {
+        Some(values[mid])
+    }
+}
+
+// 众数，出现次数相同时取较小的值
+fn mode(values: &mut [f64]) -> Option<f64> {
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mut best: Option<(f64, usize)> = None;
+    for run in values.chunk_by(|a, b| a == b) {
+        if best.is_none_or(|(_, count)| run.len() > count) {
+            best = Some((run[0], run.len()));
+        }
+    }
+    best.map(|(value, _)| value)
+}
+
+// 可选的 "was_missing" 指示列: 每个在训练数据中有缺失的被填充列，在末尾追加一列 0/1
+#[derive(Debug, Clone, Default)]
+struct Indicator {
+    enabled: bool,
+    columns: Vec<usize>,
+}
+
+impl Indicator {
+    fn fit(&mut self, x: &Matrix, candidates: &[usize]) {
+        self.columns = candidates
+            .iter()
+            .copied()
+            .filter(|&j| x.column(j).iter().any(f64::is_nan))
+            .collect();
+    }
+
+    // original 是填充前的数据，用来判断哪些位置原本缺失
+    fn apply(&self, original: &Matrix, imputed: Matrix) -> Matrix {
+        if !self.enabled || self.columns.is_empty() {
+            return imputed;
+        }
+        let cols = imputed.cols() + self.columns.len();
+        let mut data = Vec::with_capacity(imputed.rows() * cols);
+        for (i, row) in imputed.iter_rows().enumerate() {
+            data.extend_from_slice(row);
+            data.extend(self.columns.iter().map(|&j| if original[(i, j)].is_nan() { 1.0 } else { 0.0 }));
+        }
+        Matrix::from_vec(imputed.rows(), cols, data).expect("指示列的形状由构造保证")
+    }
+
+    // 输入列名加上指示列的名称 "<列名>_was_missing"
+    fn names(&self, input: &[String]) -> Vec<String> {
+        let mut names = input.to_vec();
+        names.extend(self.columns().iter().map(|&j| format!("{}_was_missing", input[j])));
+        names
+    }
+
+    // 被追加指示列的原始列下标，按追加顺序
+    fn columns(&self) -> &[usize] {
+        if self.enabled {
+            &self.columns
+        } else {
+            &[]
+        }
+    }
+}
+
+fn check_width(name: &str, x: &Matrix, fitted: usize) {
+    assert_eq!(x.cols(), fitted, "{} 未 fit 或特征数不一致", name);
+}
+
+// ---------------------------
+// 按列统计量填充
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    Mean,
+    Median,
+    MostFrequent,
+    Constant(f64),
+}
+
+#[derive(Debug, Clone)]
+pub struct SimpleImputer {
+    strategy: Strategy,
+    columns: Option<Vec<usize>>, // None 表示所有列
+    fill: Vec<(usize, f64)>,     // 学到的 (列, 填充值)
+    width: usize,
+    indicator: Indicator,
+}
+
+impl SimpleImputer {
+    pub fn new(strategy: Strategy) -> Self {
+        SimpleImputer {
+            strategy,
+            columns: None,
+            fill: Vec::new(),
+            width: 0,
+            indicator: Indicator::default(),
+        }
+    }
+
+    // 只填充这些列
+    pub fn columns(mut self, columns: &[usize]) -> Self {
+        self.columns = Some(columns.to_vec());
+        self
+    }
+
+    pub fn with_indicator(mut self) -> Self {
+        self.indicator.enabled = true;
+        self
+    }
+
+    pub fn fill_values(&self) -> &[(usize, f64)] {
+        &self.fill
+    }
+
+    pub fn indicator_columns(&self) -> &[usize] {
+        self.indicator.columns()
+    }
+}
+
+impl Transformer for SimpleImputer {
+    fn fit(&mut self, x: &Matrix, _y: &[f64]) {
+        let columns = self.columns.clone().unwrap_or_else(|| (0..x.cols()).collect());
+        self.fill = columns
+            .iter()
+            .map(|&j| {
+                let mut values = observed(x, j);
+                let value = match self.strategy {
+                    Strategy::Mean => mean(&values),
+                    Strategy::Median => median(&mut values),
+                    Strategy::MostFrequent => mode(&mut values),
+                    Strategy::Constant(c) => Some(c),
+                };
+                (j, value.unwrap_or(0.0))
+            })
+            .collect();
+        self.width = x.cols();
+        self.indicator.fit(x, &columns);
+    }
+
+    fn transform(&self, x: &Matrix) -> Matrix {
+        check_width("SimpleImputer", x, self.width);
+        let mut out = x.clone();
+        for i in 0..out.rows() {
+            let row = out.row_mut(i);
+            for &(j, value) in &self.fill {
+                if row[j].is_nan() {
+                    row[j] = value;
+                }
+            }
+        }
+        self.indicator.apply(x, out)
+    }
+
+    fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        self.indicator.names(input)
+    }
+}
+
+// ---------------------------
+// 按组中位数填充
+// ---------------------------
+// 例如按 pclass × sex（以及称谓列）分组填充年龄。
+// 分组列本身缺失或训练数据中没有见过的组，依次退回到 then_by 给出的较粗分组，最后是整列的中位数。
+#[derive(Debug, Clone)]
+pub struct GroupMedianImputer {
+    target: usize,
+    by: Vec<Vec<usize>>,                  // 各级分组列，从细到粗
+    medians: Vec<HashMap<Vec<u64>, f64>>, // 与 by 一一对应
+    fallback: f64,
+    width: usize,
+    indicator: Indicator,
+}
+
+impl GroupMedianImputer {
+    pub fn new(target: usize, by: &[usize]) -> Self {
+        GroupMedianImputer {
+            target,
+            by: vec![by.to_vec()],
+            medians: Vec::new(),
+            fallback: 0.0,
+            width: 0,
+            indicator: Indicator::default(),
+        }
+    }
+
+    // 上一级分组无法使用时改按这些列分组，例如称谓缺失时只按 pclass × sex
+    pub fn then_by(mut self, by: &[usize]) -> Self {
+        self.by.push(by.to_vec());
+        self
+    }
+
+    pub fn with_indicator(mut self) -> Self {
+        self.indicator.enabled = true;
+        self
+    }
+
+    pub fn indicator_columns(&self) -> &[usize] {
+        self.indicator.columns()
+    }
+
+    // 最细一级的分组数
+    pub fn group_count(&self) -> usize {
+        self.medians.first().map_or(0, HashMap::len)
+    }
+
+    fn key(by: &[usize], row: &[f64]) -> Option<Vec<u64>> {
+        by.iter()
+            .map(|&j| if row[j].is_nan() { None } else { Some(row[j].to_bits()) })
+            .collect()
+    }
+
+    fn lookup(&self, row: &[f64]) -> Option<f64> {
+        self.by
+            .iter()
+            .zip(&self.medians)
+            .find_map(|(by, medians)| GroupMedianImputer::key(by, row).and_then(|key| medians.get(&key).copied()))
+    }
+}
+
+impl Transformer for GroupMedianImputer {
+    fn fit(&mut self, x: &Matrix, _y: &[f64]) {
+        let target = self.target;
+        self.medians = self
+            .by
+            .iter()
+            .map(|by| {
+                let mut groups: HashMap<Vec<u64>, Vec<f64>> = HashMap::new();
+                for row in x.iter_rows() {
+                    if let (Some(key), false) = (GroupMedianImputer::key(by, row), row[target].is_nan()) {
+                        groups.entry(key).or_default().push(row[target]);
+                    }
+                }
+                groups
+                    .into_iter()
+                    .filter_map(|(key, mut values)| median(&mut values).map(|m| (key, m)))
+                    .collect()
+            })
+            .collect();
+        self.fallback = median(&mut observed(x, self.target)).unwrap_or(0.0);
+        self.width = x.cols();
+        self.indicator.fit(x, &[self.target]);
+    }
+
+    fn transform(&self, x: &Matrix) -> Matrix {
+        check_width("GroupMedianImputer", x, self.width);
+        let mut out = x.clone();
+        for i in 0..out.rows() {
+            if out[(i, self.target)].is_nan() {
+                let value = self.lookup(out.row(i)).unwrap_or(self.fallback);
+                out[(i, self.target)] = value;
+            }
+        }
+        self.indicator.apply(x, out)
+    }
+
+    fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        self.indicator.names(input)
+    }
+}
+
+// ---------------------------
+// k 近邻填充
+// ---------------------------
+// 距离只在两行都不缺失的列上计算（各列先除以训练数据的标准差，避免票价主导距离），
+// 再按实际参与的列数放大到全部列。每个缺失值取 k 个在该列有值的最近训练样本的平均。
+#[derive(Debug, Clone)]
+pub struct KnnImputer {
+    k: usize,
+    train: Matrix,
+    scales: Vec<f64>,
+    medians: Vec<f64>, // 找不到邻居时的后备值
+    indicator: Indicator,
+}
+
+impl KnnImputer {
+    pub fn new(k: usize) -> Self {
+        assert!(k > 0, "KnnImputer 的 k 必须大于 0");
+        KnnImputer {
+            k,
+            train: Matrix::default(),
+            scales: Vec::new(),
+            medians: Vec::new(),
+            indicator: Indicator::default(),
+        }
+    }
+
+    pub fn with_indicator(mut self) -> Self {
+        self.indicator.enabled = true;
+        self
+    }
+
+    pub fn indicator_columns(&self) -> &[usize] {
+        self.indicator.columns()
+    }
+
+    fn distance(&self, a: &[f64], b: &[f64]) -> Option<f64> {
+        let mut sum = 0.0;
+        let mut present = 0;
+        for ((&u, &v), &scale) in a.iter().zip(b).zip(&self.scales) {
+            if !u.is_nan() && !v.is_nan() {
+                sum += ((u - v) / scale).powi(2);
+                present += 1;
+            }
+        }
+        if present == 0 {
+            None
+        } else {
+            Some((sum * a.len() as f64 / present as f64).sqrt())
+        }
+    }
+}
+
+impl Transformer for KnnImputer {
+    fn fit(&mut self, x: &Matrix, _y: &[f64]) {
+        self.train = x.clone();
+        self.scales = (0..x.cols())
+            .map(|j| {
+                let values = observed(x, j);
+                let m = mean(&values).unwrap_or(0.0);
+                let std = mean(&values.iter().map(|v| (v - m).powi(2)).collect::<Vec<_>>())
+                    .unwrap_or(0.0)
+                    .sqrt();
+                if std > 0.0 { std } else { 1.0 }
+            })
+            .collect();
+        self.medians = (0..x.cols()).map(|j| median(&mut observed(x, j)).unwrap_or(0.0)).collect();
+        self.indicator.fit(x, &(0..x.cols()).collect::<Vec<_>>());
+    }
+
+    fn transform(&self, x: &Matrix) -> Matrix {
+        check_width("KnnImputer", x, self.train.cols());
+        let mut out = x.clone();
+        for i in 0..x.rows() {
+            let row = x.row(i);
+            if !row.iter().any(|v| v.is_nan()) {
+                continue;
+            }
+            let mut neighbours: Vec<(f64, usize)> = self
+                .train
+                .iter_rows()
+                .enumerate()
+                .filter_map(|(t, train_row)| self.distance(row, train_row).map(|d| (d, t)))
+                .collect();
+            neighbours.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+            for j in (0..x.cols()).filter(|&j| row[j].is_nan()) {
+                let values: Vec<f64> = neighbours
+                    .iter()
+                    .map(|&(_, t)| self.train[(t, j)])
+                    .filter(|v| !v.is_nan())
+                    .take(self.k)
+                    .collect();
+                out[(i, j)] = mean(&values).unwrap_or(self.medians[j]);
+            }
+        }
+        self.indicator.apply(x, out)
+    }
+
+    fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        self.indicator.names(input)
+    }
+}
+
+// ---------------------------
+// 回归填充
+// ---------------------------
+// 在目标列有值的训练样本上用最小二乘（加极小的岭项保证可解）拟合 target ~ predictors，
+// 预测值限制在训练数据中目标列的取值范围内。预测列本身缺失时先用其中位数代替。
+#[derive(Debug, Clone)]
+pub struct RegressionImputer {
+    target: usize,
+    predictors: Vec<usize>,
+    coefficients: Vec<f64>, // 截距 + 各预测列的系数
+    predictor_medians: Vec<f64>,
+    range: (f64, f64),
+    width: usize,
+    indicator: Indicator,
+}
+
+impl RegressionImputer {
+    pub fn new(target: usize, predictors: &[usize]) -> Self {
+        assert!(!predictors.contains(&target), "预测列不能包含目标列");
+        RegressionImputer {
+            target,
+            predictors: predictors.to_vec(),
+            coefficients: Vec::new(),
+            predictor_medians: Vec::new(),
+            range: (f64::NEG_INFINITY, f64::INFINITY),
+            width: 0,
+            indicator: Indicator::default(),
+        }
+    }
+
+    pub fn with_indicator(mut self) -> Self {
+        self.indicator.enabled = true;
+        self
+    }
+
+    pub fn indicator_columns(&self) -> &[usize] {
+        self.indicator.columns()
+    }
+
+    pub fn coefficients(&self) -> &[f64] {
+        &self.coefficients
+    }
+
+    // 截距在前
+    fn design_row(&self, row: &[f64]) -> Vec<f64> {
+        std::iter::once(1.0)
+            .chain(
+                self.predictors
+                    .iter()
+                    .zip(&self.predictor_medians)
+                    .map(|(&j, &m)| if row[j].is_nan() { m } else { row[j] }),
+            )
+            .collect()
+    }
+
+    fn predict_row(&self, row: &[f64]) -> f64 {
+        let z: f64 = self.design_row(row).iter().zip(&self.coefficients).map(|(a, b)| a * b).sum();
+        z.clamp(self.range.0, self.range.1)
+    }
+}
+
+// 高斯消元（部分主元）解 a * x = b，a 为 n × n
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&p, &q| a[p][col].abs().total_cmp(&a[q][col].abs())).unwrap();
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        if a[col][col].abs() < 1e-12 {
+            continue;
+        }
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (v, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *v -= factor * p;
+            }
+            b[col + 1 + offset] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for r in (0..n).rev() {
+        if a[r][r].abs() < 1e-12 {
+            continue;
+        }
+        let rest: f64 = (r + 1..n).map(|c| a[r][c] * x[c]).sum();
+        x[r] = (b[r] - rest) / a[r][r];
+    }
+    x
+}
+
+impl Transformer for RegressionImputer {
+    fn fit(&mut self, x: &Matrix, _y: &[f64]) {
+        self.predictor_medians = self.predictors.iter().map(|&j| median(&mut observed(x, j)).unwrap_or(0.0)).collect();
+        let p = self.predictors.len() + 1;
+
+        // 正规方程 (XᵀX + λI) β = Xᵀy
+        let mut xtx = vec![vec![0.0; p]; p];
+        let mut xty = vec![0.0; p];
+        for row in x.iter_rows().filter(|row| !row[self.target].is_nan()) {
+            let design = self.design_row(row);
+            for a in 0..p {
+                xty[a] += design[a] * row[self.target];
+                for b in 0..p {
+                    xtx[a][b] += design[a] * design[b];
+                }
+            }
+        }
+        for (a, row) in xtx.iter_mut().enumerate().skip(1) {
+            row[a] += 1e-6;
+        }
+        self.coefficients = solve(xtx, xty);
+
+        let targets = observed(x, self.target);
+        self.range = if targets.is_empty() {
+            (0.0, 0.0)
+        } else {
+            let min = targets.iter().copied().fold(f64::INFINITY, f64::min);
+            let max = targets.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            (min, max)
+        };
+        self.width = x.cols();
+        self.indicator.fit(x, &[self.target]);
+    }
+
+    fn transform(&self, x: &Matrix) -> Matrix {
+        check_width("RegressionImputer", x, self.width);
+        let mut out = x.clone();
+        for i in 0..out.rows() {
+            if out[(i, self.target)].is_nan() {
+                out[(i, self.target)] = self.predict_row(x.row(i));
+            }
+        }
+        self.indicator.apply(x, out)
+    }
+
+    fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        self.indicator.names(input)
+    }
+}
@@ -0,0 +1,25 @@
+// 泰坦尼克号生还预测 - 数据处理与模型库
+pub mod categorical;
+pub mod csv;
+pub mod cv;
+pub mod dataset;
+pub mod encode;
+pub mod error;
+pub mod forest;
+pub mod frame;
+pub mod hist;
+pub mod impute;
+pub mod logistic;
+pub mod matrix;
+pub mod metrics;
+pub mod model;
+pub mod passenger;
+pub mod pipeline;
+pub mod poly;
+pub mod quality;
+pub mod rng;
+pub mod scale;
+pub mod schema;
+pub mod split;
+pub mod text;
+pub mod tree;
@@ -0,0 +1,160 @@
+use crate::matrix::Matrix;
+use crate::model::default_feature_names;
+
+// ---------------------------
+// 逻辑回归
+// ---------------------------
+pub struct LogisticRegression {
+    weights: Vec<f64>, // weights[0] 是偏置，weights[j + 1] 对应第 j 个特征
+    lr: f64,
+    epochs: usize,
+    feature_names: Vec<String>,
+}
+
+impl LogisticRegression {
+    pub fn new(n_features: usize, lr: f64, epochs: usize) -> Self {
+        LogisticRegression {
+            weights: vec![0.0; n_features + 1],
+            lr,
+            epochs,
+            feature_names: Vec::new(),
+        }
+    }
+
+    pub fn set_feature_names(&mut self, names: &[String]) {
+        self.feature_names = names.to_vec();
+    }
+
+    pub fn n_features(&self) -> usize {
+        self.weights.len() - 1
+    }
+
+    // 与权重一一对应的特征名称
+    pub fn feature_names(&self) -> &[String] {
+        &self.feature_names
+    }
+
+    pub fn bias(&self) -> f64 {
+        self.weights[0]
+    }
+
+    // (特征名称, 权重)
+    pub fn coefficients(&self) -> Vec<(String, f64)> {
+        self.feature_names.iter().cloned().zip(self.weights[1..].iter().copied()).collect()
+    }
+
+    // 单个样本各特征对 logit 的贡献 w_j * x_j，按绝对值从大到小排列
+    pub fn explain(&self, x: &[f64]) -> Vec<(String, f64)> {
+        let mut contributions: Vec<(String, f64)> = self
+            .feature_names
+            .iter()
+            .zip(self.weights[1..].iter().zip(x))
+            .map(|(name, (w, xj))| (name.clone(), w * xj))
+            .collect();
+        contributions.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()));
+        contributions
+    }
+
+    fn sigmoid(&self, z: f64) -> f64 {
+        1.0 / (1.0 + (-z).exp())
+    }
+
+    // 特征数必须与训练时一致
+    fn check_shape(&self, x: &Matrix) {
+        assert_eq!(
+            x.cols() + 1,
+            self.weights.len(),
+            "逻辑回归训练时有 {} 个特征，输入有 {} 个",
+            self.weights.len() - 1,
+            x.cols()
+        );
+    }
+
+    pub fn fit(&mut self, x: &Matrix, y: &[f64]) {
+        let m = x.rows();
+        if m == 0 {
+            println!("警告: 训练数据为空，跳过训练");
+            return;
+        }
+        // 每次训练都从零权重开始，结果不受上一次 fit 的影响；特征数按训练数据确定
+        self.weights = vec![0.0; x.cols() + 1];
+        if self.feature_names.len() != x.cols() {
+            self.feature_names = default_feature_names(x.cols());
+        }
+        assert_eq!(m, y.len(), "样本数 {} 与标签数 {} 不一致", m, y.len());
+
+        println!("开始训练逻辑回归，数据量: {}", m);
+        
+        for epoch in 0..self.epochs {
+            let mut total_error = 0.0;
+            
+            for (xi, &yi) in x.iter_rows().zip(y) {
+                let mut z = self.weights[0]; // bias term
+                
+                for (w, xij) in self.weights[1..].iter().zip(xi) {
+                    z += w * xij;
+                }
+                
+                let pred = self.sigmoid(z);
+                let error = yi - pred;
+                total_error += error.abs();
+
+                // 更新权重
+                self.weights[0] += self.lr * error;
+                for (w, xij) in self.weights[1..].iter_mut().zip(xi) {
+                    *w += self.lr * error * xij;
+                }
+            }
+            
+            if epoch % 100 == 0 {
+                println!("Epoch {}: 平均误差 = {:.4}", epoch, total_error / m as f64);
+            }
+        }
+        
+        let weights: Vec<String> = self
+            .coefficients()
+            .iter()
+            .map(|(name, w)| format!("{}={:.4}", name, w))
+            .collect();
+        println!("逻辑回归训练完成，偏置 {:.4}，权重: {}", self.bias(), weights.join(", "));
+    }
+
+    pub fn predict(&self, x: &Matrix) -> Vec<f64> {
+        if x.is_empty() {
+            return Vec::new();
+        }
+        self.check_shape(x);
+
+        x.iter_rows()
+            .map(|xi| {
+                let mut z = self.weights[0];
+                for (w, xij) in self.weights[1..].iter().zip(xi) {
+                    z += w * xij;
+                }
+                let prob = self.sigmoid(z);
+                if prob >= 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+    
+    pub fn predict_proba(&self, x: &Matrix) -> Vec<f64> {
+        if x.is_empty() {
+            return Vec::new();
+        }
+        self.check_shape(x);
+
+        x.iter_rows()
+            .map(|xi| {
+                let mut z = self.weights[0];
+                for (w, xij) in self.weights[1..].iter().zip(xi) {
+                    z += w * xij;
+                }
+                self.sigmoid(z)
+            })
+            .collect()
+    }
+}
@@ -1,555 +1,109 @@
-use std::fs::File;
-use std::io::{BufReader, BufRead, stdin, stdout, Write};
-use std::path::Path;
+use std::io::{stdin, stdout, Write};
+
+use titanic_pure::dataset::{load_csv, load_csv_with, write_submission, LoadOptions, ParseMode};
+use titanic_pure::cv::cross_validate;
+use titanic_pure::encode::{ColumnEncoder, OneHotEncoder};
+use titanic_pure::error::DataError;
+use titanic_pure::forest::RandomForest;
+use titanic_pure::impute::{GroupMedianImputer, SimpleImputer, Strategy};
+use titanic_pure::logistic::LogisticRegression;
+use titanic_pure::metrics::accuracy_score;
+use titanic_pure::model::Classifier;
+use titanic_pure::passenger::{Passenger, PassengerError};
+use titanic_pure::pipeline::Pipeline;
+use titanic_pure::poly::PolynomialFeatures;
+use titanic_pure::scale::StandardScaler;
+use titanic_pure::split::{stratified_split, CvStrategy, Strata};
+use titanic_pure::text::Title;
+use titanic_pure::tree::{MaxFeatures, TreeParams};
 
 // ---------------------------
-// 数据结构
-// ---------------------------
-#[derive(Debug)]
-struct DataSet {
-    features: Vec<Vec<f64>>,
-    labels: Vec<f64>,
-}
-
-// ---------------------------
-// CSV 读取和数据预处理
-// ---------------------------
-fn load_csv(path: &str) -> Result<DataSet, Box<dyn std::error::Error>> {
-    println!("尝试加载文件: {}", path);
-    
-    let path = Path::new(path);
-    if !path.exists() {
-        // 尝试在当前目录下查找文件
-        let file_name = path.file_name().unwrap_or_default().to_str().unwrap_or("titanic.csv");
-        let current_dir = std::env::current_dir()?;
-        let new_path = current_dir.join(file_name);
-        
-        println!("也在查找: {}", new_path.display());
-        
-        if new_path.exists() {
-            println!("在当前位置找到文件: {}", new_path.display());
-            return load_csv(new_path.to_str().unwrap());
-        } else {
-            // 列出当前目录下的文件帮助用户定位
-            println!("当前目录下的文件:");
-            if let Ok(entries) = std::fs::read_dir(&current_dir) {
-                for entry in entries.flatten() {
-                    if let Ok(file_type) = entry.file_type() {
-                        if file_type.is_file() {
-                            println!("  - {}", entry.file_name().to_string_lossy());
-                        }
-                    }
-                }
-            }
-            return Err(format!("文件不存在: {}. 请确保titanic.csv文件在正确位置", path.display()).into());
-        }
-    }
-    
-    let file = File::open(path)?;
-    let reader = BufReader::new(file);
-    let mut raw_rows = Vec::new();
-    let mut ages = Vec::new();
-
-    let mut line_count = 0;
-    let mut skipped_lines = 0;
-
-    for (i, line) in reader.lines().enumerate() {
-        let line = line?;
-        line_count += 1;
-        
-        if i == 0 {
-            println!("CSV标题行: {}", line);
-            continue; // skip header
-        }
-
-        let fields: Vec<String> = line.split(',').map(|s| s.trim_matches('"').trim().to_string()).collect();
-        
-        // 调试输出前几行
-        if i <= 3 {
-            println!("第{}行数据: {:?}", i, fields);
-            println!("字段数量: {}", fields.len());
-        }
-        
-        if fields.len() < 8 {
-            println!("跳过不完整的行 {}: 只有 {} 个字段", i, fields.len());
-            skipped_lines += 1;
-            continue;
-        }
-        
-        raw_rows.push(fields.clone());
-        
-        // 收集年龄用于计算中位数 - 根据你的CSV格式，年龄在第5个字段（索引4）
-        if fields.len() > 4 && !fields[4].is_empty() && fields[4] != " " {
-            if let Ok(age) = fields[4].parse::<f64>() {
-                ages.push(age);
-            }
-        }
-    }
-
-    println!("总共读取 {} 行，跳过 {} 行不完整数据", line_count, skipped_lines);
-
-    // 计算年龄中位数
-    let median_age = if ages.is_empty() {
-        println!("警告: 没有找到有效的年龄数据，使用默认值 29.7");
-        29.7 // 默认值
-    } else {
-        ages.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        let median = ages[ages.len() / 2];
-        println!("计算得到年龄中位数: {}", median);
-        median
-    };
-
-    let mut features = Vec::new();
-    let mut labels = Vec::new();
-
-    for (i, row) in raw_rows.iter().enumerate() {
-        // 根据你的CSV格式调整字段索引：
-        // ["survived", "pclass", "name", "sex", "age", "fare", "sibsp", "parch"]
-        //    [0]        [1]      [2]     [3]    [4]    [5]     [6]      [7]
-        
-        // 解析标签 (survived) - 第1个字段
-        let survived = if row[0].is_empty() { 
-            0.0 
-        } else { 
-            row[0].parse::<f64>().unwrap_or(0.0) 
-        };
-        
-        // 解析特征
-        let pclass = row[1].parse::<f64>().unwrap_or(3.0);
-        
-        // 性别处理 - 第4个字段（索引3）
-        let sex = if row[3].to_lowercase().contains("male") { 
-            0.0 
-        } else { 
-            1.0 
-        };
-        
-        // 年龄 - 第5个字段（索引4）
-        let age = if row.len() > 4 && (row[4].is_empty() || row[4] == " ") {
-            median_age
-        } else if row.len() > 4 {
-            row[4].parse::<f64>().unwrap_or(median_age)
-        } else {
-            median_age
-        };
-        
-        // 票价 - 第6个字段（索引5）
-        let fare = if row.len() > 5 && !row[5].is_empty() { 
-            row[5].parse::<f64>().unwrap_or(0.0) 
-        } else {
-            0.0
-        };
-        
-        // sibsp - 第7个字段（索引6）
-        let sibsp = if row.len() > 6 { 
-            row[6].parse::<f64>().unwrap_or(0.0) 
-        } else {
-            0.0
-        };
-        
-        // parch - 第8个字段（索引7）
-        let parch = if row.len() > 7 { 
-            row[7].parse::<f64>().unwrap_or(0.0) 
-        } else {
-            0.0
-        };
-        
-        let family_size = sibsp + parch + 1.0;
-
-        features.push(vec![pclass, sex, age, fare, sibsp, parch, family_size]);
-        labels.push(survived);
-        
-        // 显示前几条处理后的数据用于调试
-        if i < 3 {
-            println!("处理后的第{}条数据: pclass={}, sex={}, age={}, fare={}, sibsp={}, parch={}, survived={}", 
-                     i, pclass, sex, age, fare, sibsp, parch, survived);
-        }
-    }
-
-    println!("成功处理 {} 条有效数据", features.len());
-    Ok(DataSet { features, labels })
-}
-
-// ---------------------------
-// 训练/测试拆分
+// 交互预测
 // ---------------------------
-fn train_test_split(dataset: &DataSet, test_ratio: f64) -> (DataSet, DataSet) {
-    let n = dataset.features.len();
-    let test_size = (n as f64 * test_ratio) as usize;
-    
-    let mut x_train = Vec::new();
-    let mut y_train = Vec::new();
-    let mut x_test = Vec::new();
-    let mut y_test = Vec::new();
-
-    if n == 0 {
-        return (DataSet { features: x_train, labels: y_train }, 
-                DataSet { features: x_test, labels: y_test });
-    }
+// 读入一行 key=value 格式的乘客信息，输入结束 (EOF) 时返回 None
+fn input_passenger() -> Option<Result<Passenger, PassengerError>> {
+    println!("\n请输入新乘客信息预测生还概率，例如:");
+    println!("  pclass=1 sex=female age=29 fare=211.3 sibsp=0 parch=0");
+    println!("(pclass 和 sex 必填，其余可省略)");
+    print!("> ");
+    stdout().flush().unwrap();
 
-    // 为了更好的随机性，我们可以打乱数据
-    use std::collections::HashSet;
-    let mut used_indices = HashSet::new();
-    let mut test_indices = Vec::new();
-    
-    // 随机选择测试集索引
-    while test_indices.len() < test_size {
-        let idx = (test_indices.len() % n) as usize; // 简单轮询，实际应该用随机数
-        if !used_indices.contains(&idx) {
-            test_indices.push(idx);
-            used_indices.insert(idx);
-        }
-    }
-    
-    for i in 0..n {
-        if test_indices.contains(&i) {
-            x_test.push(dataset.features[i].clone());
-            y_test.push(dataset.labels[i]);
-        } else {
-            x_train.push(dataset.features[i].clone());
-            y_train.push(dataset.labels[i]);
-        }
+    let mut input = String::new();
+    match stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input.parse()),
     }
-
-    (DataSet { features: x_train, labels: y_train }, 
-     DataSet { features: x_test, labels: y_test })
 }
 
-// ---------------------------
-// 逻辑回归
-// ---------------------------
-struct LogisticRegression {
-    weights: Vec<f64>,
-    lr: f64,
-    epochs: usize,
-}
-
-impl LogisticRegression {
-    fn new(n_features: usize, lr: f64, epochs: usize) -> Self {
-        LogisticRegression {
-            weights: vec![0.0; n_features + 1],
-            lr,
-            epochs,
-        }
-    }
-
-    fn sigmoid(&self, z: f64) -> f64 {
-        1.0 / (1.0 + (-z).exp())
-    }
-
-    fn fit(&mut self, x: &Vec<Vec<f64>>, y: &Vec<f64>) {
-        let m = x.len();
-        if m == 0 {
-            println!("警告: 训练数据为空，跳过训练");
-            return;
-        }
-
-        println!("开始训练逻辑回归，数据量: {}", m);
-        
-        for epoch in 0..self.epochs {
-            let mut total_error = 0.0;
-            
-            for i in 0..m {
-                let xi = &x[i];
-                let mut z = self.weights[0]; // bias term
-                
-                for j in 0..xi.len() {
-                    z += self.weights[j + 1] * xi[j];
-                }
-                
-                let pred = self.sigmoid(z);
-                let error = y[i] - pred;
-                total_error += error.abs();
-
-                // 更新权重
-                self.weights[0] += self.lr * error;
-                for j in 0..xi.len() {
-                    self.weights[j + 1] += self.lr * error * xi[j];
-                }
-            }
-            
-            if epoch % 100 == 0 {
-                println!("Epoch {}: 平均误差 = {:.4}", epoch, total_error / m as f64);
-            }
-        }
-        
-        println!("逻辑回归训练完成，最终权重: {:?}", self.weights);
-    }
-
-    fn predict(&self, x: &Vec<Vec<f64>>) -> Vec<f64> {
-        if x.is_empty() {
-            return Vec::new();
+// 姓名派生的列中追加到特征矩阵的部分；title 的字典固定，训练数据和待预测数据的编号一致
+const FRAME_FEATURES: [&str; 1] = ["title"];
+
+// 年龄按 pclass × sex（有 title 列时再细分到称谓，Master 基本都是男孩）分组的中位数填充并标记缺失，
+// 称谓未知时（如交互输入）退回到 pclass × sex；title 做 one-hot 编码，称谓未知时全为 0；
+// 其余缺失值用整列中位数填充；
+// crosses 中的每组特征相乘追加为交叉项（用舱位等级数字相乘，在舱位编码之前做）；
+// 舱位是类别而不是连续数值，最后做 one-hot 编码（以头等舱为参照）。
+// title 的 one-hot 在原位置展开成若干列（列数取决于训练数据），交叉项和舱位编码按原始列下标取列，
+// 所以这些列必须排在 title 之前，不满足时直接报错而不是悄悄取错列
+fn preprocess<M: Classifier>(model: M, names: &[String], crosses: &[&[&str]]) -> Pipeline<M> {
+    let col = |name: &str| names.iter().position(|n| n == name);
+    let required = |name: &str| col(name).unwrap();
+    let title = col("title");
+    let before_title = |name: &str| {
+        let j = required(name);
+        assert!(title.is_none_or(|t| j < t), "{} 排在 title 之后，title 编码后列下标会变化", name);
+        j
+    };
+    let by = [required("pclass"), required("sex")];
+    let mut pipeline = Pipeline::new(model);
+    match title {
+        Some(title) => {
+            let title_labels: Vec<String> = Title::ALL.iter().map(|t| t.label().to_string()).collect();
+            let imputer = GroupMedianImputer::new(required("age"), &[by[0], by[1], title]).then_by(&by);
+            pipeline = pipeline
+                .with_step(imputer.with_indicator())
+                .with_step(ColumnEncoder::new(title, OneHotEncoder::new()).labels(&title_labels));
         }
-        
-        x.iter()
-            .map(|xi| {
-                let mut z = self.weights[0];
-                for j in 0..xi.len() {
-                    z += self.weights[j + 1] * xi[j];
-                }
-                let prob = self.sigmoid(z);
-                if prob >= 0.5 {
-                    1.0
-                } else {
-                    0.0
-                }
-            })
-            .collect()
+        None => pipeline = pipeline.with_step(GroupMedianImputer::new(required("age"), &by).with_indicator()),
     }
-    
-    fn predict_proba(&self, x: &Vec<Vec<f64>>) -> Vec<f64> {
-        if x.is_empty() {
-            return Vec::new();
-        }
-        
-        x.iter()
-            .map(|xi| {
-                let mut z = self.weights[0];
-                for j in 0..xi.len() {
-                    z += self.weights[j + 1] * xi[j];
-                }
-                self.sigmoid(z)
-            })
-            .collect()
+    pipeline = pipeline.with_step(SimpleImputer::new(Strategy::Median));
+    if !crosses.is_empty() {
+        let columns = |names: &[&str]| names.iter().map(|&name| before_title(name)).collect::<Vec<_>>();
+        let interactions = crosses
+            .iter()
+            .fold(PolynomialFeatures::new(1), |poly, names| poly.cross(&columns(names)));
+        pipeline = pipeline.with_step(interactions);
     }
+    pipeline.with_step(ColumnEncoder::new(before_title("pclass"), OneHotEncoder::new().drop_first()))
 }
 
-// ---------------------------
-// 随机森林（极简版）
-// ---------------------------
-struct DecisionTree {
-    feature_index: usize,
-    threshold: f64,
-    left_label: f64,
-    right_label: f64,
-}
-
-impl DecisionTree {
-    fn train(x: &Vec<Vec<f64>>, y: &Vec<f64>) -> Self {
-        if x.is_empty() {
-            return DecisionTree {
-                feature_index: 0,
-                threshold: 0.0,
-                left_label: 0.0,
-                right_label: 0.0,
+// 创建一个未训练的模型（含预处理）
+type Factory = Box<dyn Fn() -> Box<dyn Classifier>>;
+
+// 参与训练和比较的模型；新增模型只需在这里加一项
+// 缺失值填充放在流水线里，只在训练部分上学习填充值
+fn classifiers(feature_names: &[String], seed: u64) -> Vec<Factory> {
+    let n_features = feature_names.len();
+    let (lr_names, rf_names) = (feature_names.to_vec(), feature_names.to_vec());
+    // 性别和年龄的影响随舱位等级变化，线性模型需要显式的交叉项；树模型自己能学到交互，不需要
+    let lr_crosses: &[&[&str]] = &[&["sex", "pclass"], &["age", "pclass"]];
+    vec![
+        // 逻辑回归的 SGD 对特征尺度敏感，额外做标准化；缩放参数随流水线保存，交互预测时同样生效
+        Box::new(move || {
+            let lr = LogisticRegression::new(n_features, 0.01, 1000);
+            Box::new(preprocess(lr, &lr_names, lr_crosses).with_step(StandardScaler::new()))
+        }),
+        // 限制深度和叶子大小，叶子上的比例才是可靠的概率估计
+        Box::new(move || {
+            let params = TreeParams {
+                max_depth: Some(6),
+                min_samples_leaf: 3,
+                max_features: MaxFeatures::Sqrt,
+                ..TreeParams::default()
             };
-        }
-        
-        let n_features = x[0].len();
-        let mut best_feature = 0;
-        let mut best_thresh = 0.0;
-        let mut best_score = -1.0;
-        let mut left_label = 0.0;
-        let mut right_label = 0.0;
-
-        for f in 0..n_features {
-            let mut vals: Vec<f64> = x.iter().map(|x_i| x_i[f]).collect();
-            vals.sort_by(|a, b| a.partial_cmp(b).unwrap());
-            // 取一些候选阈值，避免处理所有唯一值
-            let step = vals.len() / 10;
-            if step == 0 { continue; }
-            
-            for i in (0..vals.len()).step_by(step) {
-                let t = vals[i];
-                let mut left_count = 0.0;
-                let mut right_count = 0.0;
-                let mut left_total = 0.0;
-                let mut right_total = 0.0;
-
-                for i in 0..x.len() {
-                    if x[i][f] <= t {
-                        left_total += 1.0;
-                        left_count += y[i];
-                    } else {
-                        right_total += 1.0;
-                        right_count += y[i];
-                    }
-                }
-
-                // 避免除零
-                if left_total == 0.0 || right_total == 0.0 {
-                    continue;
-                }
-                
-                let left_avg = left_count / left_total;
-                let right_avg = right_count / right_total;
-                
-                // 使用信息增益作为评分
-                let parent_impurity = 1.0 - (left_count + right_count).powi(2) / (left_total + right_total).powi(2);
-                let left_impurity = 1.0 - left_avg.powi(2) - (1.0 - left_avg).powi(2);
-                let right_impurity = 1.0 - right_avg.powi(2) - (1.0 - right_avg).powi(2);
-                
-                let info_gain = parent_impurity - (left_total * left_impurity + right_total * right_impurity) / (left_total + right_total);
-
-                if info_gain > best_score {
-                    best_score = info_gain;
-                    best_feature = f;
-                    best_thresh = t;
-                    left_label = if left_avg >= 0.5 { 1.0 } else { 0.0 };
-                    right_label = if right_avg >= 0.5 { 1.0 } else { 0.0 };
-                }
-            }
-        }
-
-        DecisionTree {
-            feature_index: best_feature,
-            threshold: best_thresh,
-            left_label,
-            right_label,
-        }
-    }
-
-    fn predict(&self, x: &Vec<f64>) -> f64 {
-        if x.is_empty() {
-            return 0.0;
-        }
-        if x[self.feature_index] <= self.threshold {
-            self.left_label
-        } else {
-            self.right_label
-        }
-    }
-}
-
-struct RandomForest {
-    n_trees: usize,
-    trees: Vec<DecisionTree>,
-}
-
-impl RandomForest {
-    fn new(n_trees: usize) -> Self {
-        RandomForest {
-            n_trees,
-            trees: Vec::new(),
-        }
-    }
-
-    fn fit(&mut self, x: &Vec<Vec<f64>>, y: &Vec<f64>) {
-        if x.is_empty() {
-            println!("警告: 训练数据为空，跳过随机森林训练");
-            return;
-        }
-        
-        println!("开始训练随机森林，数据量: {}", x.len());
-        
-        for i in 0..self.n_trees {
-            let tree = DecisionTree::train(x, y);
-            self.trees.push(tree);
-            if (i + 1) % 5 == 0 {
-                println!("已训练 {} 棵树", i + 1);
-            }
-        }
-    }
-
-    fn predict(&self, x: &Vec<Vec<f64>>) -> Vec<f64> {
-        if x.is_empty() {
-            return Vec::new();
-        }
-        
-        x.iter()
-            .map(|xi| {
-                let mut votes = 0.0;
-                for tree in &self.trees {
-                    votes += tree.predict(xi);
-                }
-                if votes / (self.trees.len() as f64) >= 0.5 {
-                    1.0
-                } else {
-                    0.0
-                }
-            })
-            .collect()
-    }
-    
-    fn predict_proba(&self, x: &Vec<Vec<f64>>) -> Vec<f64> {
-        if x.is_empty() {
-            return Vec::new();
-        }
-        
-        x.iter()
-            .map(|xi| {
-                let mut votes = 0.0;
-                for tree in &self.trees {
-                    votes += tree.predict(xi);
-                }
-                votes / (self.trees.len() as f64)
-            })
-            .collect()
-    }
-}
-
-// ---------------------------
-// 评估函数
-// ---------------------------
-fn accuracy_score(y_true: &Vec<f64>, y_pred: &Vec<f64>) -> f64 {
-    if y_true.is_empty() || y_pred.is_empty() {
-        return 0.0;
-    }
-    
-    let mut correct = 0.0;
-    for i in 0..y_true.len() {
-        if (y_true[i] - y_pred[i]).abs() < 1e-6 {
-            correct += 1.0;
-        }
-    }
-    correct / y_true.len() as f64
-}
-
-// ---------------------------
-// 交互预测
-// ---------------------------
-fn input_passenger() -> Option<Vec<f64>> {
-    let mut input = String::new();
-    
-    println!("\n请输入新乘客信息预测生还概率:");
-    
-    print!("舱位等级(1/2/3): ");
-    stdout().flush().unwrap();
-    input.clear();
-    stdin().read_line(&mut input).ok()?;
-    let pclass: f64 = input.trim().parse().ok()?;
-    if pclass < 1.0 || pclass > 3.0 {
-        println!("舱位等级必须是1, 2或3");
-        return None;
-    }
-
-    print!("性别(male/female): ");
-    stdout().flush().unwrap();
-    input.clear();
-    stdin().read_line(&mut input).ok()?;
-    let sex = if input.trim().to_lowercase() == "male" {
-        0.0
-    } else {
-        1.0
-    };
-
-    print!("年龄: ");
-    stdout().flush().unwrap();
-    input.clear();
-    stdin().read_line(&mut input).ok()?;
-    let age: f64 = input.trim().parse().ok()?;
-
-    print!("票价: ");
-    stdout().flush().unwrap();
-    input.clear();
-    stdin().read_line(&mut input).ok()?;
-    let fare: f64 = input.trim().parse().ok()?;
-
-    print!("兄弟姐妹/配偶数: ");
-    stdout().flush().unwrap();
-    input.clear();
-    stdin().read_line(&mut input).ok()?;
-    let sibsp: f64 = input.trim().parse().ok()?;
-
-    print!("父母/子女数: ");
-    stdout().flush().unwrap();
-    input.clear();
-    stdin().read_line(&mut input).ok()?;
-    let parch: f64 = input.trim().parse().ok()?;
-
-    let family_size = sibsp + parch + 1.0;
-
-    Some(vec![pclass, sex, age, fare, sibsp, parch, family_size])
+            Box::new(preprocess(RandomForest::new(10).seed(seed).tree_params(params), &rf_names, &[])) // 10棵树
+        }),
+    ]
 }
 
 // ---------------------------
@@ -559,11 +113,31 @@ fn main() {
     println!("泰坦尼克号生还预测系统");
     println!("=====================");
     
-    // 加载数据 - 使用相对路径
-    let dataset = match load_csv("titanic.csv") {
+    // 命令行参数: [--strict] [--quality-json] [--seed=N] [训练数据, 默认 titanic.csv] [待预测数据, 如 Kaggle test.csv]
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let strict = args.iter().any(|a| a == "--strict");
+    let seed = args
+        .iter()
+        .find_map(|a| a.strip_prefix("--seed="))
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or(42);
+    let paths: Vec<&String> = args.iter().filter(|a| !a.starts_with("--")).collect();
+    let train_path = paths.first().map(|s| s.as_str()).unwrap_or("titanic.csv");
+    let predict_path = paths.get(1);
+
+    // 加载数据 - 使用相对路径；--strict 时遇到任何坏字段都直接报错
+    let loaded = if strict {
+        load_csv_with(train_path, &LoadOptions::new(ParseMode::Strict)).map(|(data, _)| data)
+    } else {
+        load_csv(train_path)
+    };
+    let mut dataset = match loaded {
         Ok(data) => data,
         Err(e) => {
             println!("错误: {}", e);
+            if !matches!(e, DataError::FileNotFound(_)) {
+                return;
+            }
             println!("\n请下载泰坦尼克号数据集并保存为 'titanic.csv'");
             println!("数据集下载地址: https://www.kaggle.com/c/titanic/data");
             println!("或者使用以下命令创建示例数据文件:");
@@ -579,48 +153,129 @@ fn main() {
         println!("请检查CSV文件格式是否正确");
         return;
     }
+
+    if !dataset.is_labeled() {
+        println!("错误: 训练数据缺少 survived 列，无法训练模型");
+        println!("Kaggle 的 test.csv 请作为第二个参数传入用于预测");
+        return;
+    }
     
-    println!("成功加载 {} 条数据", dataset.features.len());
+    println!("成功加载 {} 条数据", dataset.len());
+
+    // 精简格式没有 name 列，也就没有 title
+    let frame_features: Vec<&str> = FRAME_FEATURES
+        .into_iter()
+        .filter(|name| dataset.frame.column(name).is_ok())
+        .collect();
+    if let Err(e) = dataset.add_frame_features(&frame_features) {
+        println!("错误: {}", e);
+        return;
+    }
+
+    // 训练前先检查数据质量；--quality-json 时同时导出 JSON
+    let quality = dataset.quality_report();
+    println!("\n{}", quality);
+    if args.iter().any(|a| a == "--quality-json") {
+        match std::fs::write("quality_report.json", quality.to_json()) {
+            Ok(()) => println!("数据质量报告已写入 quality_report.json"),
+            Err(e) => println!("写入 quality_report.json 失败: {}", e),
+        }
+    }
     
-    // 拆分训练测试集
-    let (train, test) = train_test_split(&dataset, 0.2);
-    println!("训练集: {} 条, 测试集: {} 条", train.features.len(), test.features.len());
+    // 按 survived × pclass × sex 分层拆分训练测试集，保持各部分的类别比例
+    let (train, test) = stratified_split(&dataset, 0.2, seed, Strata::LabelPclassSex);
+    println!("训练集: {} 条, 测试集: {} 条 (随机种子 {})", train.len(), test.len(), seed);
 
     if train.features.is_empty() {
         println!("错误: 训练集为空，无法训练模型");
         return;
     }
 
-    // 训练逻辑回归
-    println!("\n训练逻辑回归模型中...");
-    let mut lr = LogisticRegression::new(train.features[0].len(), 0.01, 1000);
-    lr.fit(&train.features, &train.labels);
-    let lr_pred = lr.predict(&test.features);
-    println!("逻辑回归准确率: {:.2}%", accuracy_score(&test.labels, &lr_pred) * 100.0);
+    // 训练并在测试集上评估每个模型
+    let factories = classifiers(&dataset.feature_names, seed);
+    let mut models: Vec<(Box<dyn Classifier>, f64)> = Vec::new();
+    for factory in &factories {
+        let mut model = factory();
+        println!("\n训练{}模型中...", model.name());
+        model.set_feature_names(&train.feature_names);
+        model.fit(&train.features, &train.labels);
+        let importances = model.feature_importances();
+        if !importances.is_empty() {
+            println!("{}特征重要性:", model.name());
+            for (name, importance) in importances {
+                println!("  {:<20} {:.4}", name, importance);
+            }
+        }
+        let pred = model.predict(&test.features);
+        let acc = accuracy_score(&test.labels, &pred);
+        println!("{}准确率: {:.2}%", model.name(), acc * 100.0);
+        models.push((model, acc));
+    }
+
+    // 5 折分层交叉验证，比单次拆分更稳定地估计模型效果；样本不足 5 条时减少折数
+    let k = dataset.len().min(5);
+    if k < 2 {
+        println!("\n样本数不足 2 条，跳过交叉验证");
+    } else {
+        println!("\n{} 折分层交叉验证...", k);
+        let cv = CvStrategy::StratifiedKFold { k, seed, strata: Strata::Label };
+        let reports: Vec<_> = factories.iter().map(|factory| cross_validate(&dataset, &cv, factory)).collect();
+        for ((model, _), report) in models.iter().zip(&reports) {
+            println!("\n{}交叉验证结果:\n{}", model.name(), report);
+        }
+    }
 
-    // 训练随机森林
-    println!("\n训练随机森林模型中...");
-    let mut rf = RandomForest::new(10); // 10棵树
-    rf.fit(&train.features, &train.labels);
-    let rf_pred = rf.predict(&test.features);
-    println!("随机森林准确率: {:.2}%", accuracy_score(&test.labels, &rf_pred) * 100.0);
+    // 对无标签数据批量预测，输出 Kaggle 提交文件
+    if let Some(predict_path) = predict_path {
+        println!("\n加载待预测数据...");
+        // 待预测数据需要与训练数据相同的追加列
+        let loaded = load_csv(predict_path)
+            .and_then(|mut unlabeled| unlabeled.add_frame_features(&frame_features).map(|_| unlabeled).map_err(Into::into));
+        match loaded {
+            Ok(unlabeled) => {
+                // 测试集准确率最高的模型，并列时取列表中靠前的
+                let best = models
+                    .iter()
+                    .fold(&models[0], |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+                let predictions = best.0.predict(&unlabeled.features);
+                match write_submission("submission.csv", &unlabeled.passenger_ids, &predictions) {
+                    Ok(()) => println!("已使用{}预测 {} 名乘客，结果写入 submission.csv", best.0.name(), predictions.len()),
+                    Err(e) => println!("写入 submission.csv 失败: {}", e),
+                }
+            }
+            Err(e) => println!("错误: {}", e),
+        }
+    }
 
     // 交互预测
     println!("\n开始交互式预测...");
     loop {
-        if let Some(x_new) = input_passenger() {
-            let rf_proba = rf.predict_proba(&vec![x_new.clone()])[0];
-            let rf_label = rf.predict(&vec![x_new.clone()])[0];
-            let lr_proba = lr.predict_proba(&vec![x_new.clone()])[0];
-            let lr_label = lr.predict(&vec![x_new.clone()])[0];
-            
-            println!("随机森林预测 - 生还概率: {:.2}%, 预测结果: {}", 
-                     rf_proba * 100.0, if rf_label == 1.0 { "生还" } else { "未生还" });
-            println!("逻辑回归预测 - 生还概率: {:.2}%, 预测结果: {}", 
-                     lr_proba * 100.0, if lr_label == 1.0 { "生还" } else { "未生还" });
-        } else {
-            println!("输入有误，请重新输入");
-            continue;
+        // 省略的年龄和票价由流水线用训练集学到的中位数填充
+        let passenger = match input_passenger() {
+            Some(Ok(passenger)) => passenger,
+            Some(Err(e)) => {
+                println!("输入有误: {}，请重新输入", e);
+                continue;
+            }
+            None => break,
+        };
+
+        // 交互输入没有姓名，追加的列（称谓）由模型记为缺失
+        for (model, _) in &models {
+            let proba = model.predict_passenger_proba(&passenger);
+            let label = model.predict_passenger(&passenger);
+            println!("{}预测 - 生还概率: {:.2}%, 预测结果: {}",
+                     model.name(), proba * 100.0, if label == 1.0 { "生还" } else { "未生还" });
+            // 支持解释的模型给出影响最大的几项特征
+            let contributions: Vec<String> = model
+                .explain(&model.passenger_features(&passenger))
+                .iter()
+                .take(3)
+                .map(|(name, c)| format!("{} {:+.3}", name, c))
+                .collect();
+            if !contributions.is_empty() {
+                println!("{}主要依据: {}", model.name(), contributions.join(", "));
+            }
         }
 
         let mut input = String::new();
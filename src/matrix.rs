@@ -0,0 +1,187 @@
+use std::error::Error;
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// ---------------------------
+// 稠密矩阵（行优先，单块连续存储）
+// ---------------------------
+// 第 i 行第 j 列存放在 data[i * cols + j]，整行是一段连续切片，整列按 cols 步长访问
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Matrix {
+    data: Vec<f64>,
+    rows: usize,
+    cols: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    DataLength { rows: usize, cols: usize, len: usize }, // data 长度不等于 rows * cols
+    RaggedRow { row: usize, expected: usize, found: usize }, // 某一行的列数与第一行不同
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapeError::DataLength { rows, cols, len } => {
+                write!(f, "{} × {} 的矩阵需要 {} 个元素，实际为 {}", rows, cols, rows * cols, len)
+            }
+            ShapeError::RaggedRow { row, expected, found } => {
+                write!(f, "第 {} 行有 {} 列，应为 {} 列", row, found, expected)
+            }
+        }
+    }
+}
+
+impl Error for ShapeError {}
+
+impl Matrix {
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Matrix { data: vec![0.0; rows * cols], rows, cols }
+    }
+
+    pub fn from_vec(rows: usize, cols: usize, data: Vec<f64>) -> Result<Self, ShapeError> {
+        if data.len() != rows * cols {
+            return Err(ShapeError::DataLength { rows, cols, len: data.len() });
+        }
+        Ok(Matrix { data, rows, cols })
+    }
+
+    // 从逐行的数据构造，所有行的长度必须相同
+    pub fn from_rows(rows: &[Vec<f64>]) -> Result<Self, ShapeError> {
+        let cols = rows.first().map_or(0, Vec::len);
+        let mut data = Vec::with_capacity(rows.len() * cols);
+        for (i, row) in rows.iter().enumerate() {
+            if row.len() != cols {
+                return Err(ShapeError::RaggedRow { row: i, expected: cols, found: row.len() });
+            }
+            data.extend_from_slice(row);
+        }
+        Ok(Matrix { data, rows: rows.len(), cols })
+    }
+
+    // 单个样本组成的 1 × n 矩阵
+    pub fn from_row(row: &[f64]) -> Self {
+        Matrix { data: row.to_vec(), rows: 1, cols: row.len() }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    // 没有样本
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<f64> {
+        if row < self.rows && col < self.cols {
+            Some(self.data[row * self.cols + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, i: usize) -> &[f64] {
+        assert!(i < self.rows, "行下标越界: {} >= {}", i, self.rows);
+        &self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [f64] {
+        assert!(i < self.rows, "行下标越界: {} >= {}", i, self.rows);
+        &mut self.data[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn iter_rows(&self) -> impl ExactSizeIterator<Item = &[f64]> + '_ {
+        (0..self.rows).map(move |i| &self.data[i * self.cols..(i + 1) * self.cols])
+    }
+
+    pub fn column(&self, j: usize) -> ColumnView<'_> {
+        assert!(j < self.cols, "列下标越界: {} >= {}", j, self.cols);
+        ColumnView { matrix: self, col: j }
+    }
+
+    // 追加一行，列数必须一致（空矩阵的第一行决定列数）
+    pub fn push_row(&mut self, row: &[f64]) -> Result<(), ShapeError> {
+        if self.rows == 0 && self.data.is_empty() {
+            self.cols = row.len();
+        }
+        if row.len() != self.cols {
+            return Err(ShapeError::RaggedRow { row: self.rows, expected: self.cols, found: row.len() });
+        }
+        self.data.extend_from_slice(row);
+        self.rows += 1;
+        Ok(())
+    }
+
+    // 按行下标取子矩阵（顺序与 indices 一致，可以重复）
+    pub fn select_rows(&self, indices: &[usize]) -> Matrix {
+        let mut data = Vec::with_capacity(indices.len() * self.cols);
+        for &i in indices {
+            data.extend_from_slice(self.row(i));
+        }
+        Matrix { data, rows: indices.len(), cols: self.cols }
+    }
+
+    pub fn to_rows(&self) -> Vec<Vec<f64>> {
+        self.iter_rows().map(<[f64]>::to_vec).collect()
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (row, col): (usize, usize)) -> &f64 {
+        assert!(row < self.rows && col < self.cols, "下标 ({}, {}) 超出 {} × {} 矩阵", row, col, self.rows, self.cols);
+        &self.data[row * self.cols + col]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut f64 {
+        assert!(row < self.rows && col < self.cols, "下标 ({}, {}) 超出 {} × {} 矩阵", row, col, self.rows, self.cols);
+        &mut self.data[row * self.cols + col]
+    }
+}
+
+// 按步长访问的一列，不复制数据
+#[derive(Debug, Clone, Copy)]
+pub struct ColumnView<'a> {
+    matrix: &'a Matrix,
+    col: usize,
+}
+
+impl<'a> ColumnView<'a> {
+    pub fn len(&self) -> usize {
+        self.matrix.rows
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matrix.rows == 0
+    }
+
+    pub fn get(&self, i: usize) -> f64 {
+        self.matrix[(i, self.col)]
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = f64> + 'a {
+        let (col, stride, rows) = (self.col, self.matrix.cols, self.matrix.rows);
+        let data = &self.matrix.data;
+        (0..rows).map(move |i| data[i * stride + col])
+    }
+
+    pub fn to_vec(&self) -> Vec<f64> {
+        self.iter().collect()
+    }
+}
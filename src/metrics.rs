@@ -0,0 +1,66 @@
+// ---------------------------
+// 评估函数
+// ---------------------------
+pub fn accuracy_score(y_true: &[f64], y_pred: &[f64]) -> f64 {
+    if y_true.is_empty() || y_pred.is_empty() {
+        return 0.0;
+    }
+    
+    let mut correct = 0.0;
+    for i in 0..y_true.len() {
+        if (y_true[i] - y_pred[i]).abs() < 1e-6 {
+            correct += 1.0;
+        }
+    }
+    correct / y_true.len() as f64
+}
+
+// 混淆矩阵计数，正类为 1.0
+fn confusion_counts(y_true: &[f64], y_pred: &[f64]) -> (f64, f64, f64) {
+    let mut tp = 0.0;
+    let mut fp = 0.0;
+    let mut fn_ = 0.0;
+    for (&t, &p) in y_true.iter().zip(y_pred) {
+        match (t == 1.0, p == 1.0) {
+            (true, true) => tp += 1.0,
+            (false, true) => fp += 1.0,
+            (true, false) => fn_ += 1.0,
+            (false, false) => {}
+        }
+    }
+    (tp, fp, fn_)
+}
+
+// 没有预测出正类时记为 0
+pub fn precision_score(y_true: &[f64], y_pred: &[f64]) -> f64 {
+    let (tp, fp, _) = confusion_counts(y_true, y_pred);
+    if tp + fp == 0.0 { 0.0 } else { tp / (tp + fp) }
+}
+
+// 测试集中没有正类时记为 0
+pub fn recall_score(y_true: &[f64], y_pred: &[f64]) -> f64 {
+    let (tp, _, fn_) = confusion_counts(y_true, y_pred);
+    if tp + fn_ == 0.0 { 0.0 } else { tp / (tp + fn_) }
+}
+
+pub fn f1_score(y_true: &[f64], y_pred: &[f64]) -> f64 {
+    let p = precision_score(y_true, y_pred);
+    let r = recall_score(y_true, y_pred);
+    if p + r == 0.0 { 0.0 } else { 2.0 * p * r / (p + r) }
+}
+
+// 对数损失，概率裁剪到 [1e-15, 1 - 1e-15] 避免 ln(0)
+pub fn log_loss(y_true: &[f64], y_proba: &[f64]) -> f64 {
+    if y_true.is_empty() {
+        return 0.0;
+    }
+    let total: f64 = y_true
+        .iter()
+        .zip(y_proba)
+        .map(|(&t, &p)| {
+            let p = p.clamp(1e-15, 1.0 - 1e-15);
+            -(t * p.ln() + (1.0 - t) * (1.0 - p).ln())
+        })
+        .sum();
+    total / y_true.len() as f64
+}
@@ -0,0 +1,167 @@
+use crate::forest::RandomForest;
+use crate::logistic::LogisticRegression;
+use crate::matrix::Matrix;
+use crate::passenger::Passenger;
+
+// ---------------------------
+// 分类器接口
+// ---------------------------
+// 评估、交叉验证和交互预测只依赖这个接口，新增模型只需实现它并注册到 main 的模型列表
+pub trait Classifier {
+    // 用于输出的模型名称
+    fn name(&self) -> &str;
+
+    // 期望的输入特征数（训练后为训练数据的列数）
+    fn n_features(&self) -> usize;
+
+    // 在 fit 之前设置特征名称，用于输出权重、分裂和重要性；不设置时使用 x0, x1, ...
+    fn set_feature_names(&mut self, names: &[String]);
+
+    fn fit(&mut self, x: &Matrix, y: &[f64]);
+
+    // 每个样本为正类（生还）的概率
+    fn predict_proba(&self, x: &Matrix) -> Vec<f64>;
+
+    // 概率不低于 threshold 的样本判为正类
+    fn predict_with_threshold(&self, x: &Matrix, threshold: f64) -> Vec<f64> {
+        self.predict_proba(x)
+            .into_iter()
+            .map(|p| if p >= threshold { 1.0 } else { 0.0 })
+            .collect()
+    }
+
+    fn predict(&self, x: &Matrix) -> Vec<f64> {
+        self.predict_with_threshold(x, 0.5)
+    }
+
+    // (特征名称, 重要性)，按重要性从大到小排列；不支持的模型返回空
+    fn feature_importances(&self) -> Vec<(String, f64)> {
+        Vec::new()
+    }
+
+    // 单个样本各特征对预测的贡献，按影响从大到小排列；不支持的模型返回空
+    fn explain(&self, _x: &[f64]) -> Vec<(String, f64)> {
+        Vec::new()
+    }
+
+    // 单个乘客的特征行。Passenger 只有 FEATURE_NAMES 的 7 列，训练数据在其后追加的列
+    // （如姓名派生的 title）在这里记为 NaN，与缺失的年龄、票价一样交给流水线的填充步骤处理
+    fn passenger_features(&self, passenger: &Passenger) -> Vec<f64> {
+        let mut row = passenger.to_features();
+        assert!(
+            row.len() <= self.n_features(),
+            "{}有 {} 个输入特征，少于乘客信息的 {} 列（模型未训练，或不是用乘客特征训练的）",
+            self.name(),
+            self.n_features(),
+            row.len()
+        );
+        row.resize(self.n_features(), f64::NAN);
+        row
+    }
+
+    // 单个乘客的生还概率。缺失的年龄、票价和追加的列为 NaN，
+    // 模型本身不含填充步骤（不是 Pipeline）时需要调用方先填充
+    fn predict_passenger_proba(&self, passenger: &Passenger) -> f64 {
+        self.predict_proba(&Matrix::from_row(&self.passenger_features(passenger)))[0]
+    }
+
+    fn predict_passenger(&self, passenger: &Passenger) -> f64 {
+        self.predict(&Matrix::from_row(&self.passenger_features(passenger)))[0]
+    }
+}
+
+// 没有设置名称或名称数量与特征数不一致时使用的默认名称
+pub fn default_feature_names(n: usize) -> Vec<String> {
+    (0..n).map(|j| format!("x{}", j)).collect()
+}
+
+// 装箱的分类器，便于把不同模型放进同一个列表
+impl<C: Classifier + ?Sized> Classifier for Box<C> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn n_features(&self) -> usize {
+        (**self).n_features()
+    }
+
+    fn set_feature_names(&mut self, names: &[String]) {
+        (**self).set_feature_names(names)
+    }
+
+    fn fit(&mut self, x: &Matrix, y: &[f64]) {
+        (**self).fit(x, y)
+    }
+
+    fn predict_proba(&self, x: &Matrix) -> Vec<f64> {
+        (**self).predict_proba(x)
+    }
+
+    fn predict_with_threshold(&self, x: &Matrix, threshold: f64) -> Vec<f64> {
+        (**self).predict_with_threshold(x, threshold)
+    }
+
+    fn predict(&self, x: &Matrix) -> Vec<f64> {
+        (**self).predict(x)
+    }
+
+    fn feature_importances(&self) -> Vec<(String, f64)> {
+        (**self).feature_importances()
+    }
+
+    fn explain(&self, x: &[f64]) -> Vec<(String, f64)> {
+        (**self).explain(x)
+    }
+}
+
+impl Classifier for LogisticRegression {
+    fn name(&self) -> &str {
+        "逻辑回归"
+    }
+
+    fn n_features(&self) -> usize {
+        LogisticRegression::n_features(self)
+    }
+
+    fn set_feature_names(&mut self, names: &[String]) {
+        LogisticRegression::set_feature_names(self, names)
+    }
+
+    fn fit(&mut self, x: &Matrix, y: &[f64]) {
+        LogisticRegression::fit(self, x, y)
+    }
+
+    fn predict_proba(&self, x: &Matrix) -> Vec<f64> {
+        LogisticRegression::predict_proba(self, x)
+    }
+
+    fn explain(&self, x: &[f64]) -> Vec<(String, f64)> {
+        LogisticRegression::explain(self, x)
+    }
+}
+
+impl Classifier for RandomForest {
+    fn name(&self) -> &str {
+        "随机森林"
+    }
+
+    fn n_features(&self) -> usize {
+        RandomForest::n_features(self)
+    }
+
+    fn set_feature_names(&mut self, names: &[String]) {
+        RandomForest::set_feature_names(self, names)
+    }
+
+    fn fit(&mut self, x: &Matrix, y: &[f64]) {
+        RandomForest::fit(self, x, y)
+    }
+
+    fn predict_proba(&self, x: &Matrix) -> Vec<f64> {
+        RandomForest::predict_proba(self, x)
+    }
+
+    fn feature_importances(&self) -> Vec<(String, f64)> {
+        RandomForest::feature_importances(self)
+    }
+}
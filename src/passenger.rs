@@ -0,0 +1,264 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::categorical::{CategoryError, Vocabulary};
+use crate::schema::Column;
+
+// ---------------------------
+// 乘客
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pclass {
+    First,
+    Second,
+    Third,
+}
+
+impl Pclass {
+    // Vocabulary::pclass() 的编号: "1" = 0, "2" = 1, "3" = 2
+    pub fn from_code(code: usize) -> Option<Pclass> {
+        match code {
+            0 => Some(Pclass::First),
+            1 => Some(Pclass::Second),
+            2 => Some(Pclass::Third),
+            _ => None,
+        }
+    }
+
+    // 舱位等级数字 1..3
+    pub fn number(self) -> u8 {
+        match self {
+            Pclass::First => 1,
+            Pclass::Second => 2,
+            Pclass::Third => 3,
+        }
+    }
+}
+
+impl fmt::Display for Pclass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Pclass {
+    type Err = CategoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = Vocabulary::pclass().parse(s)?.code();
+        Ok(code.and_then(Pclass::from_code).expect("pclass 词表不接受未知取值"))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Sex {
+    Male,
+    Female,
+}
+
+impl Sex {
+    // Vocabulary::sex() 的编号: male = 0, female = 1
+    pub fn from_code(code: usize) -> Option<Sex> {
+        match code {
+            0 => Some(Sex::Male),
+            1 => Some(Sex::Female),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> usize {
+        match self {
+            Sex::Male => 0,
+            Sex::Female => 1,
+        }
+    }
+}
+
+impl fmt::Display for Sex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Sex::Male => write!(f, "male"),
+            Sex::Female => write!(f, "female"),
+        }
+    }
+}
+
+impl FromStr for Sex {
+    type Err = CategoryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let code = Vocabulary::sex().parse(s)?.code();
+        Ok(code.and_then(Sex::from_code).expect("sex 词表不接受未知取值"))
+    }
+}
+
+// 年龄和票价可以缺失，由调用方决定如何填充
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Passenger {
+    pub pclass: Pclass,
+    pub sex: Sex,
+    pub age: Option<f64>,
+    pub fare: Option<f64>,
+    pub sibsp: u32,
+    pub parch: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PassengerError {
+    MissingField(&'static str),
+    InvalidValue { field: &'static str, value: String, reason: String },
+    OutOfRange { field: &'static str, value: f64, min: f64, max: f64 },
+    UnknownKey(String),
+}
+
+impl fmt::Display for PassengerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PassengerError::MissingField(field) => write!(f, "缺少字段 {}", field),
+            PassengerError::InvalidValue { field, value, reason } => {
+                write!(f, "字段 {} 的值 '{}' 无效: {}", field, value, reason)
+            }
+            PassengerError::OutOfRange { field, value, min, max } => {
+                write!(f, "字段 {} 的值 {} 超出范围 [{}, {}]", field, value, min, max)
+            }
+            PassengerError::UnknownKey(key) => write!(f, "未知字段 '{}'", key),
+        }
+    }
+}
+
+impl Error for PassengerError {}
+
+// 合理取值范围
+const AGE_RANGE: (f64, f64) = (0.0, 120.0);
+const FARE_RANGE: (f64, f64) = (0.0, 10_000.0);
+
+fn check_range(field: &'static str, value: Option<f64>, (min, max): (f64, f64)) -> Result<(), PassengerError> {
+    match value {
+        Some(v) if !(min..=max).contains(&v) => Err(PassengerError::OutOfRange { field, value: v, min, max }),
+        _ => Ok(()),
+    }
+}
+
+impl Passenger {
+    pub fn builder() -> PassengerBuilder {
+        PassengerBuilder::default()
+    }
+
+    pub fn validate(&self) -> Result<(), PassengerError> {
+        check_range("age", self.age, AGE_RANGE)?;
+        check_range("fare", self.fare, FARE_RANGE)
+    }
+
+    pub fn family_size(&self) -> u32 {
+        self.sibsp + self.parch + 1
+    }
+
+    // 特征向量，顺序与 dataset::FEATURE_NAMES 一致；缺失的年龄和票价为 NaN
+    pub fn to_features(&self) -> Vec<f64> {
+        vec![
+            self.pclass.number() as f64,
+            self.sex.code() as f64,
+            self.age.unwrap_or(f64::NAN),
+            self.fare.unwrap_or(f64::NAN),
+            self.sibsp as f64,
+            self.parch as f64,
+            self.family_size() as f64,
+        ]
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PassengerBuilder {
+    pclass: Option<Pclass>,
+    sex: Option<Sex>,
+    age: Option<f64>,
+    fare: Option<f64>,
+    sibsp: u32,
+    parch: u32,
+}
+
+impl PassengerBuilder {
+    pub fn pclass(mut self, pclass: Pclass) -> Self {
+        self.pclass = Some(pclass);
+        self
+    }
+
+    pub fn sex(mut self, sex: Sex) -> Self {
+        self.sex = Some(sex);
+        self
+    }
+
+    pub fn age(mut self, age: f64) -> Self {
+        self.age = Some(age);
+        self
+    }
+
+    pub fn fare(mut self, fare: f64) -> Self {
+        self.fare = Some(fare);
+        self
+    }
+
+    pub fn sibsp(mut self, sibsp: u32) -> Self {
+        self.sibsp = sibsp;
+        self
+    }
+
+    pub fn parch(mut self, parch: u32) -> Self {
+        self.parch = parch;
+        self
+    }
+
+    // pclass 和 sex 必填，亲属数默认为 0
+    pub fn build(self) -> Result<Passenger, PassengerError> {
+        let passenger = Passenger {
+            pclass: self.pclass.ok_or(PassengerError::MissingField("pclass"))?,
+            sex: self.sex.ok_or(PassengerError::MissingField("sex"))?,
+            age: self.age,
+            fare: self.fare,
+            sibsp: self.sibsp,
+            parch: self.parch,
+        };
+        passenger.validate()?;
+        Ok(passenger)
+    }
+}
+
+fn parse_value<T>(field: &'static str, value: &str) -> Result<T, PassengerError>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    value.parse().map_err(|e: T::Err| PassengerError::InvalidValue {
+        field,
+        value: value.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+// 紧凑的 key=value 格式，字段之间用空白或逗号分隔，键名接受 CSV 标题的别名:
+// "pclass=1 sex=female age=29 fare=211.3 sibsp=0 parch=0"
+impl FromStr for Passenger {
+    type Err = PassengerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut builder = Passenger::builder();
+        for pair in s.split(|c: char| c.is_whitespace() || c == ',').filter(|p| !p.is_empty()) {
+            let (key, value) = pair.split_once('=').ok_or_else(|| PassengerError::InvalidValue {
+                field: "input",
+                value: pair.to_string(),
+                reason: "应为 key=value".to_string(),
+            })?;
+            builder = match Column::from_header(key) {
+                Some(Column::Pclass) => builder.pclass(parse_value("pclass", value)?),
+                Some(Column::Sex) => builder.sex(parse_value("sex", value)?),
+                Some(Column::Age) => builder.age(parse_value("age", value)?),
+                Some(Column::Fare) => builder.fare(parse_value("fare", value)?),
+                Some(Column::SibSp) => builder.sibsp(parse_value("sibsp", value)?),
+                Some(Column::Parch) => builder.parch(parse_value("parch", value)?),
+                _ => return Err(PassengerError::UnknownKey(key.to_string())),
+            };
+        }
+        builder.build()
+    }
+}
@@ -0,0 +1,120 @@
+use crate::matrix::Matrix;
+use crate::model::Classifier;
+
+// ---------------------------
+// 预处理接口
+// ---------------------------
+// fit 只在训练数据上学习统计量（中位数、均值等），transform 用学到的统计量处理任意数据，
+// 这样测试集和预测时的输入不会影响训练。
+pub trait Transformer {
+    fn fit(&mut self, x: &Matrix, y: &[f64]);
+
+    fn transform(&self, x: &Matrix) -> Matrix;
+
+    fn fit_transform(&mut self, x: &Matrix, y: &[f64]) -> Matrix {
+        self.fit(x, y);
+        self.transform(x)
+    }
+
+    // 输出各列的名称。默认列不变；增加、替换列的步骤（如编码器）需要重写。
+    fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        input.to_vec()
+    }
+}
+
+// ---------------------------
+// 流水线: 若干预处理步骤 + 最终模型
+// ---------------------------
+pub struct Pipeline<M: Classifier> {
+    steps: Vec<Box<dyn Transformer>>,
+    model: M,
+    n_features: usize,
+    input_names: Vec<String>,
+}
+
+impl<M: Classifier> Pipeline<M> {
+    pub fn new(model: M) -> Self {
+        Pipeline {
+            steps: Vec::new(),
+            model,
+            n_features: 0,
+            input_names: Vec::new(),
+        }
+    }
+
+    // 按添加顺序依次执行
+    pub fn with_step(mut self, step: impl Transformer + 'static) -> Self {
+        self.steps.push(Box::new(step));
+        self
+    }
+
+    pub fn model(&self) -> &M {
+        &self.model
+    }
+
+    // 经过所有预处理步骤后的特征名称
+    pub fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        self.steps.iter().fold(input.to_vec(), |names, step| step.feature_names_out(&names))
+    }
+
+    // 只执行预处理步骤，得到模型实际看到的特征
+    pub fn transform(&self, x: &Matrix) -> Matrix {
+        let mut current = x.clone();
+        for step in &self.steps {
+            current = step.transform(&current);
+        }
+        current
+    }
+}
+
+impl<M: Classifier> Classifier for Pipeline<M> {
+    fn name(&self) -> &str {
+        self.model.name()
+    }
+
+    // 预处理之前的原始特征数
+    fn n_features(&self) -> usize {
+        self.n_features
+    }
+
+    // 记录输入特征的名称，fit 时经过各步骤换算成模型看到的名称
+    fn set_feature_names(&mut self, names: &[String]) {
+        self.input_names = names.to_vec();
+    }
+
+    fn fit(&mut self, x: &Matrix, y: &[f64]) {
+        self.n_features = x.cols();
+        let mut current = x.clone();
+        for step in &mut self.steps {
+            current = step.fit_transform(&current, y);
+        }
+        // 编码器等步骤的输出名称要在 fit 之后才能确定
+        if self.input_names.len() == x.cols() {
+            let names = self.feature_names_out(&self.input_names);
+            self.model.set_feature_names(&names);
+        }
+        self.model.fit(&current, y);
+    }
+
+    fn predict_proba(&self, x: &Matrix) -> Vec<f64> {
+        self.model.predict_proba(&self.transform(x))
+    }
+
+    fn predict_with_threshold(&self, x: &Matrix, threshold: f64) -> Vec<f64> {
+        self.model.predict_with_threshold(&self.transform(x), threshold)
+    }
+
+    fn predict(&self, x: &Matrix) -> Vec<f64> {
+        self.model.predict(&self.transform(x))
+    }
+
+    // 名称已在 fit 时换算成模型看到的特征
+    fn feature_importances(&self) -> Vec<(String, f64)> {
+        self.model.feature_importances()
+    }
+
+    // x 是原始特征，先经过预处理再由模型解释
+    fn explain(&self, x: &[f64]) -> Vec<(String, f64)> {
+        self.model.explain(self.transform(&Matrix::from_row(x)).row(0))
+    }
+}
@@ -0,0 +1,133 @@
+use crate::matrix::Matrix;
+use crate::pipeline::Transformer;
+
+// ---------------------------
+// 多项式与交叉特征
+// ---------------------------
+// 线性模型只能给每个特征一个固定的斜率，表达不了 "女性的生还优势在三等舱小得多" 这类交互作用。
+// 在原始列之后追加若干乘积项，每一项是若干列（可重复）的乘积，例如 age^2、sex*pclass。
+// 原始列保持不变；乘积中有 NaN 时结果为 NaN，所以这一步应放在缺失值填充之后。
+#[derive(Debug, Clone)]
+pub struct PolynomialFeatures {
+    degree: usize,
+    interaction_only: bool,
+    columns: Option<Vec<usize>>, // None 表示所有列
+    crosses: Vec<Vec<usize>>,   // 用户指定的交叉项
+    terms: Vec<Vec<usize>>,     // fit 后确定的乘积项，每项内的列下标升序
+    width: usize,
+}
+
+impl PolynomialFeatures {
+    // degree = 1 时不生成任何乘积项，只保留 cross 指定的交叉项
+    pub fn new(degree: usize) -> Self {
+        assert!(degree >= 1, "多项式次数至少为 1");
+        PolynomialFeatures {
+            degree,
+            interaction_only: false,
+            columns: None,
+            crosses: Vec::new(),
+            terms: Vec::new(),
+            width: 0,
+        }
+    }
+
+    // 只生成不同列之间的乘积，不生成 x^2 这样的幂次项
+    pub fn interaction_only(mut self) -> Self {
+        self.interaction_only = true;
+        self
+    }
+
+    // 只对这些列展开，其余列原样保留
+    pub fn columns(mut self, columns: &[usize]) -> Self {
+        self.columns = Some(columns.to_vec());
+        self
+    }
+
+    // 追加一个指定的交叉项，例如 cross(&[sex, pclass])
+    pub fn cross(mut self, columns: &[usize]) -> Self {
+        assert!(columns.len() >= 2, "交叉项至少包含两列");
+        self.crosses.push(columns.to_vec());
+        self
+    }
+
+    // 生成的乘积项（不含原始列）
+    pub fn terms(&self) -> &[Vec<usize>] {
+        &self.terms
+    }
+
+    // 按次数从低到高、同次数内按列下标字典序生成乘积项
+    fn expand(&self, columns: &[usize]) -> Vec<Vec<usize>> {
+        let mut terms = Vec::new();
+        let mut level: Vec<Vec<usize>> = columns.iter().map(|&j| vec![j]).collect();
+        let mut positions: Vec<usize> = (0..columns.len()).collect(); // 每项最后一列在 columns 中的位置
+        for _ in 2..=self.degree {
+            let mut next = Vec::new();
+            let mut next_positions = Vec::new();
+            for (term, &last) in level.iter().zip(&positions) {
+                let start = if self.interaction_only { last + 1 } else { last };
+                for (p, &j) in columns.iter().enumerate().skip(start) {
+                    let mut product = term.clone();
+                    product.push(j);
+                    next.push(product);
+                    next_positions.push(p);
+                }
+            }
+            terms.extend(next.iter().cloned());
+            level = next;
+            positions = next_positions;
+        }
+        terms
+    }
+
+    fn term_name(term: &[usize], input: &[String]) -> String {
+        let mut parts: Vec<String> = Vec::new();
+        let mut i = 0;
+        while i < term.len() {
+            let power = term[i..].iter().take_while(|&&j| j == term[i]).count();
+            let name = &input[term[i]];
+            parts.push(if power == 1 { name.clone() } else { format!("{}^{}", name, power) });
+            i += power;
+        }
+        parts.join("*")
+    }
+}
+
+impl Transformer for PolynomialFeatures {
+    fn fit(&mut self, x: &Matrix, _y: &[f64]) {
+        let mut columns = self.columns.clone().unwrap_or_else(|| (0..x.cols()).collect());
+        columns.sort_unstable();
+        columns.dedup();
+        let mut terms = self.expand(&columns);
+        // 指定的交叉项与展开结果重复时只保留一份
+        for cross in &self.crosses {
+            let mut term = cross.clone();
+            term.sort_unstable();
+            if !terms.contains(&term) {
+                terms.push(term);
+            }
+        }
+        if let Some(&j) = terms.iter().flatten().find(|&&j| j >= x.cols()) {
+            panic!("PolynomialFeatures 的列下标越界: {} >= {}", j, x.cols());
+        }
+        self.terms = terms;
+        self.width = x.cols();
+    }
+
+    fn transform(&self, x: &Matrix) -> Matrix {
+        assert_eq!(x.cols(), self.width, "PolynomialFeatures 未 fit 或特征数不一致");
+        let cols = x.cols() + self.terms.len();
+        let mut data = Vec::with_capacity(x.rows() * cols);
+        for row in x.iter_rows() {
+            data.extend_from_slice(row);
+            data.extend(self.terms.iter().map(|term| term.iter().map(|&j| row[j]).product::<f64>()));
+        }
+        Matrix::from_vec(x.rows(), cols, data).expect("乘积项的形状由构造保证")
+    }
+
+    // 原始列名之后是乘积项的名称，例如 "age^2"、"sex*pclass"
+    fn feature_names_out(&self, input: &[String]) -> Vec<String> {
+        let mut names = input.to_vec();
+        names.extend(self.terms.iter().map(|term| PolynomialFeatures::term_name(term, input)));
+        names
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::categorical::Vocabulary;
+use crate::dataset::{DataSet, Vocabularies};
+use crate::schema::Column;
+
+// ---------------------------
+// 数据质量报告
+// ---------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnQuality {
+    pub column: Column,
+    pub missing: usize,
+    pub unparsable: usize,
+    pub imputed: usize,      // 需要填充的值（缺失或无法解析的数值特征）
+    pub out_of_range: usize, // 能解析但不合理的值，如负年龄、零票价
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QualityReport {
+    pub rows: usize,
+    pub skipped_rows: usize,   // 加载时被跳过的记录，其字段也计入各列的统计
+    pub duplicate_rows: usize, // 除 passengerid 外所有字段都与前面某行相同
+    pub survived: usize,
+    pub died: usize,
+    pub columns: Vec<ColumnQuality>,
+}
+
+#[derive(PartialEq)]
+enum FieldCheck {
+    Missing,
+    Unparsable,
+    OutOfRange,
+    Valid,
+}
+
+fn check_number(raw: &str, in_range: impl Fn(f64) -> bool) -> FieldCheck {
+    match raw.parse::<f64>() {
+        Ok(v) if in_range(v) => FieldCheck::Valid,
+        Ok(_) => FieldCheck::OutOfRange,
+        Err(_) => FieldCheck::Unparsable,
+    }
+}
+
+fn check_category(raw: &str, vocab: &Vocabulary) -> FieldCheck {
+    match vocab.parse(raw) {
+        Ok(_) => FieldCheck::Valid,
+        Err(_) => FieldCheck::Unparsable,
+    }
+}
+
+// 单个字段的检查规则，与 load_csv 的解析方式保持一致（分类列使用加载时的同一套词表）
+fn check_field(column: Column, raw: &str, vocabularies: &Vocabularies) -> FieldCheck {
+    let raw = raw.trim();
+    if raw.is_empty() {
+        return FieldCheck::Missing;
+    }
+    match column {
+        Column::PassengerId => match raw.parse::<u32>() {
+            Ok(_) => FieldCheck::Valid,
+            Err(_) => FieldCheck::Unparsable,
+        },
+        Column::Survived => check_number(raw, |v| v == 0.0 || v == 1.0),
+        Column::Pclass => check_category(raw, &vocabularies.pclass),
+        Column::Sex => check_category(raw, &vocabularies.sex),
+        Column::Embarked => check_category(raw, &vocabularies.embarked),
+        Column::Age => check_number(raw, |v| (0.0..=120.0).contains(&v)),
+        Column::Fare => check_number(raw, |v| v > 0.0),
+        // 负数和小数在加载时按无法解析处理（记为缺失），这里同样计为无法解析
+        Column::SibSp | Column::Parch => match raw.parse::<f64>() {
+            Ok(v) if v >= 0.0 && v.fract() == 0.0 => FieldCheck::Valid,
+            _ => FieldCheck::Unparsable,
+        },
+        Column::Name | Column::Ticket | Column::Cabin => FieldCheck::Valid,
+    }
+}
+
+// 缺失或无法解析时需要填充的数值特征列（age、fare 由流水线填充，sibsp、parch 按 0 处理）
+fn is_imputed_column(column: Column) -> bool {
+    matches!(column, Column::Age | Column::Fare | Column::SibSp | Column::Parch)
+}
+
+impl QualityReport {
+    pub fn from_dataset(dataset: &DataSet) -> Self {
+        let raw = &dataset.raw;
+
+        let mut columns: Vec<ColumnQuality> = raw
+            .columns
+            .iter()
+            .map(|&column| ColumnQuality {
+                column,
+                missing: 0,
+                unparsable: 0,
+                imputed: 0,
+                out_of_range: 0,
+            })
+            .collect();
+
+        // 各列统计包括被跳过的记录，否则导致记录被跳过的字段（如无法识别的性别）反而统计不到；
+        // 被跳过的记录不参与训练，其中的缺失值不需要填充，不计入 imputed
+        let kept = raw.rows.iter().map(|row| (row, true));
+        for (row, is_kept) in kept.chain(raw.rejected.iter().map(|row| (row, false))) {
+            for (quality, value) in columns.iter_mut().zip(row) {
+                let check = check_field(quality.column, value, &dataset.vocabularies);
+                match check {
+                    FieldCheck::Missing => quality.missing += 1,
+                    FieldCheck::Unparsable => quality.unparsable += 1,
+                    FieldCheck::OutOfRange => quality.out_of_range += 1,
+                    FieldCheck::Valid => {}
+                }
+                if is_kept
+                    && is_imputed_column(quality.column)
+                    && (check == FieldCheck::Missing || check == FieldCheck::Unparsable)
+                {
+                    quality.imputed += 1;
+                }
+            }
+        }
+
+        // 重复行: 忽略 passengerid，其余原始字段完全相同
+        let id_index = raw.columns.iter().position(|&c| c == Column::PassengerId);
+        let mut seen = HashSet::new();
+        let mut duplicate_rows = 0;
+        for row in &raw.rows {
+            let key: Vec<&str> = row
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| Some(*j) != id_index)
+                .map(|(_, v)| v.as_str())
+                .collect();
+            if !seen.insert(key) {
+                duplicate_rows += 1;
+            }
+        }
+
+        let survived = dataset.labels.iter().filter(|&&y| y == 1.0).count();
+        let died = dataset.labels.len() - survived;
+
+        QualityReport {
+            rows: dataset.len(),
+            skipped_rows: raw.rejected.len() + raw.unreadable,
+            duplicate_rows,
+            survived,
+            died,
+            columns,
+        }
+    }
+
+    // 生还比例，无标签数据返回 None
+    pub fn survival_rate(&self) -> Option<f64> {
+        let total = self.survived + self.died;
+        if total == 0 {
+            None
+        } else {
+            Some(self.survived as f64 / total as f64)
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        let columns: Vec<String> = self
+            .columns
+            .iter()
+            .map(|c| {
+                format!(
+                    "    {{\"name\": \"{}\", \"missing\": {}, \"unparsable\": {}, \"imputed\": {}, \"out_of_range\": {}}}",
+                    c.column, c.missing, c.unparsable, c.imputed, c.out_of_range
+                )
+            })
+            .collect();
+        let rate = match self.survival_rate() {
+            Some(rate) => format!("{:.4}", rate),
+            None => "null".to_string(),
+        };
+        format!(
+            "{{\n  \"rows\": {},\n  \"skipped_rows\": {},\n  \"duplicate_rows\": {},\n  \"labels\": {{\"survived\": {}, \"died\": {}, \"survival_rate\": {}}},\n  \"columns\": [\n{}\n  ]\n}}",
+            self.rows,
+            self.skipped_rows,
+            self.duplicate_rows,
+            self.survived,
+            self.died,
+            rate,
+            columns.join(",\n")
+        )
+    }
+}
+
+impl fmt::Display for QualityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "数据质量报告: {} 行, {} 行重复, 加载时跳过 {} 行",
+            self.rows, self.duplicate_rows, self.skipped_rows
+        )?;
+        match self.survival_rate() {
+            Some(rate) => writeln!(
+                f,
+                "标签分布: 生还 {} / 未生还 {} (生还率 {:.1}%)",
+                self.survived,
+                self.died,
+                rate * 100.0
+            )?,
+            None => writeln!(f, "标签分布: 无标签")?,
+        }
+        writeln!(
+            f,
+            "{:<12} {:>8} {:>10} {:>8} {:>12}",
+            "column", "missing", "unparsable", "imputed", "out_of_range"
+        )?;
+        for c in &self.columns {
+            writeln!(
+                f,
+                "{:<12} {:>8} {:>10} {:>8} {:>12}",
+                c.column.name(),
+                c.missing,
+                c.unparsable,
+                c.imputed,
+                c.out_of_range
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+// ---------------------------
+// 可复现的伪随机数生成器 (xoshiro256**)
+// ---------------------------
+// 只依赖标准库。同一个种子总是产生同样的序列，用于数据拆分、抽样等需要复现的场景。
+#[derive(Debug, Clone)]
+pub struct Rng {
+    s: [u64; 4],
+    spare_normal: Option<f64>, // Box-Muller 每次产生两个正态数，缓存第二个
+}
+
+// 用 splitmix64 把一个 64 位种子扩展成 xoshiro 的 256 位状态
+fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        let mut sm = seed;
+        Rng {
+            s: [
+                splitmix64(&mut sm),
+                splitmix64(&mut sm),
+                splitmix64(&mut sm),
+                splitmix64(&mut sm),
+            ],
+            spare_normal: None,
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+
+        result
+    }
+
+    // [0, 1) 上的均匀分布，取高 53 位保证精度
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    // [low, high) 上的均匀分布
+    pub fn uniform(&mut self, low: f64, high: f64) -> f64 {
+        low + (high - low) * self.next_f64()
+    }
+
+    // [0, n) 上的均匀整数，用拒绝采样消除取模偏差
+    pub fn gen_range(&mut self, n: usize) -> usize {
+        assert!(n > 0, "gen_range 的上界必须大于 0");
+        let n = n as u64;
+        let zone = u64::MAX - u64::MAX % n;
+        loop {
+            let x = self.next_u64();
+            if x < zone {
+                return (x % n) as usize;
+            }
+        }
+    }
+
+    // 正态分布 N(mean, std^2)，Box-Muller 变换
+    pub fn normal(&mut self, mean: f64, std: f64) -> f64 {
+        if let Some(z) = self.spare_normal.take() {
+            return mean + std * z;
+        }
+        let u1 = 1.0 - self.next_f64(); // (0, 1]，避免 ln(0)
+        let u2 = self.next_f64();
+        let r = (-2.0 * u1.ln()).sqrt();
+        let theta = 2.0 * std::f64::consts::PI * u2;
+        self.spare_normal = Some(r * theta.sin());
+        mean + std * r * theta.cos()
+    }
+
+    // Fisher-Yates 洗牌
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_range(i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    // 从 0..n 中不放回地抽取 k 个下标（部分 Fisher-Yates），k 超过 n 时返回全部
+    pub fn sample_without_replacement(&mut self, n: usize, k: usize) -> Vec<usize> {
+        let k = k.min(n);
+        let mut indices: Vec<usize> = (0..n).collect();
+        for i in 0..k {
+            let j = i + self.gen_range(n - i);
+            indices.swap(i, j);
+        }
+        indices.truncate(k);
+        indices
+    }
+
+    // 从 0..n 中有放回地抽取 k 个下标（bootstrap）
+    pub fn sample_with_replacement(&mut self, n: usize, k: usize) -> Vec<usize> {
+        if n == 0 {
+            return Vec::new();
+        }
+        (0..k).map(|_| self.gen_range(n)).collect()
+    }
+}
@@ -0,0 +1,135 @@
+use std::marker::PhantomData;
+
+use crate::matrix::Matrix;
+use crate::pipeline::Transformer;
+
+// ---------------------------
+// 特征缩放
+// ---------------------------
+// 三种缩放都是逐列的 (x - center) / scale，只是 center 和 scale 的统计方式不同，
+// 由 ScaleStat 决定。统计量只在训练数据上学习并随流水线保存；NaN 不参与统计，变换后仍为 NaN。
+// 训练数据中取值恒定的列 scale 记为 1，避免除零。
+pub trait ScaleStat {
+    const NAME: &'static str;
+
+    // 一列非 NaN 的训练数据（非空，可以原地排序）的 (center, scale)
+    fn center_scale(values: &mut [f64]) -> (f64, f64);
+}
+
+#[derive(Debug, Clone)]
+pub struct Scaler<S: ScaleStat> {
+    columns: Option<Vec<usize>>,    // None 表示所有列
+    params: Vec<(usize, f64, f64)>, // (列, center, scale)
+    width: usize,
+    stat: PhantomData<S>,
+}
+
+pub type StandardScaler = Scaler<Standard>;
+pub type MinMaxScaler = Scaler<MinMax>;
+pub type RobustScaler = Scaler<Robust>;
+
+impl<S: ScaleStat> Scaler<S> {
+    pub fn new() -> Self {
+        Scaler {
+            columns: None,
+            params: Vec::new(),
+            width: 0,
+            stat: PhantomData,
+        }
+    }
+
+    // 只缩放这些列，例如跳过 0/1 指示列
+    pub fn columns(mut self, columns: &[usize]) -> Self {
+        self.columns = Some(columns.to_vec());
+        self
+    }
+
+    // 学到的 (列, center, scale)
+    pub fn params(&self) -> &[(usize, f64, f64)] {
+        &self.params
+    }
+}
+
+impl<S: ScaleStat> Default for Scaler<S> {
+    fn default() -> Self {
+        Scaler::new()
+    }
+}
+
+impl<S: ScaleStat> Transformer for Scaler<S> {
+    fn fit(&mut self, x: &Matrix, _y: &[f64]) {
+        let columns = self.columns.clone().unwrap_or_else(|| (0..x.cols()).collect());
+        self.params = columns
+            .into_iter()
+            .map(|j| {
+                let mut values: Vec<f64> = x.column(j).iter().filter(|v| !v.is_nan()).collect();
+                let (center, scale) = if values.is_empty() { (0.0, 1.0) } else { S::center_scale(&mut values) };
+                let scale = if scale.is_finite() && scale > 0.0 { scale } else { 1.0 };
+                (j, center, scale)
+            })
+            .collect();
+        self.width = x.cols();
+    }
+
+    fn transform(&self, x: &Matrix) -> Matrix {
+        assert_eq!(x.cols(), self.width, "{} 未 fit 或特征数不一致", S::NAME);
+        let mut out = x.clone();
+        for i in 0..out.rows() {
+            let row = out.row_mut(i);
+            for &(j, center, scale) in &self.params {
+                row[j] = (row[j] - center) / scale;
+            }
+        }
+        out
+    }
+}
+
+// 排序后数据的分位数，线性插值
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let pos = q * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (pos - lower as f64)
+}
+
+// 标准化: 减均值、除以标准差
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Standard;
+
+impl ScaleStat for Standard {
+    const NAME: &'static str = "StandardScaler";
+
+    fn center_scale(values: &mut [f64]) -> (f64, f64) {
+        let n = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / n;
+        let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+        (mean, var.sqrt())
+    }
+}
+
+// 缩放到 [0, 1]: 减最小值、除以极差。预测数据超出训练范围时结果也会超出 [0, 1]。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MinMax;
+
+impl ScaleStat for MinMax {
+    const NAME: &'static str = "MinMaxScaler";
+
+    fn center_scale(values: &mut [f64]) -> (f64, f64) {
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        (min, max - min)
+    }
+}
+
+// 稳健缩放: 减中位数、除以四分位距 (IQR)，不受票价等长尾列中极端值的影响
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Robust;
+
+impl ScaleStat for Robust {
+    const NAME: &'static str = "RobustScaler";
+
+    fn center_scale(values: &mut [f64]) -> (f64, f64) {
+        values.sort_by(|a, b| a.total_cmp(b));
+        (quantile(values, 0.5), quantile(values, 0.75) - quantile(values, 0.25))
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+// ---------------------------
+// 列定义
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Column {
+    PassengerId,
+    Survived,
+    Pclass,
+    Name,
+    Sex,
+    Age,
+    SibSp,
+    Parch,
+    Ticket,
+    Fare,
+    Cabin,
+    Embarked,
+}
+
+impl Column {
+    // 与 Kaggle train.csv 的列顺序一致
+    pub const ALL: [Column; 12] = [
+        Column::PassengerId,
+        Column::Survived,
+        Column::Pclass,
+        Column::Name,
+        Column::Sex,
+        Column::Age,
+        Column::SibSp,
+        Column::Parch,
+        Column::Ticket,
+        Column::Fare,
+        Column::Cabin,
+        Column::Embarked,
+    ];
+
+    // 规范列名（小写）
+    pub fn name(self) -> &'static str {
+        match self {
+            Column::PassengerId => "passengerid",
+            Column::Survived => "survived",
+            Column::Pclass => "pclass",
+            Column::Name => "name",
+            Column::Sex => "sex",
+            Column::Age => "age",
+            Column::SibSp => "sibsp",
+            Column::Parch => "parch",
+            Column::Ticket => "ticket",
+            Column::Fare => "fare",
+            Column::Cabin => "cabin",
+            Column::Embarked => "embarked",
+        }
+    }
+
+    // 可以接受的别名，比较前都会经过 normalize_header
+    fn aliases(self) -> &'static [&'static str] {
+        match self {
+            Column::PassengerId => &["passengerid", "id"],
+            Column::Survived => &["survived", "survival"],
+            Column::Pclass => &["pclass", "class", "ticketclass"],
+            Column::Name => &["name", "passengername"],
+            Column::Sex => &["sex", "gender"],
+            Column::Age => &["age"],
+            Column::SibSp => &["sibsp", "siblingsspouses"],
+            Column::Parch => &["parch", "parentschildren"],
+            Column::Ticket => &["ticket", "ticketnumber"],
+            Column::Fare => &["fare", "ticketfare"],
+            Column::Cabin => &["cabin", "cabinnumber"],
+            Column::Embarked => &["embarked", "portofembarkation"],
+        }
+    }
+
+    // 根据标题行中的列名查找对应的列
+    pub fn from_header(header: &str) -> Option<Column> {
+        let key = normalize_header(header);
+        Column::ALL
+            .iter()
+            .copied()
+            .find(|c| c.aliases().contains(&key.as_str()))
+    }
+}
+
+impl fmt::Display for Column {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// 忽略大小写、空格、下划线和连字符: "SibSp" / "sib_sp" / "Sib Sp" 都视为同一列
+fn normalize_header(header: &str) -> String {
+    header
+        .trim()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+// ---------------------------
+// 结构错误
+// ---------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub enum SchemaError {
+    MissingHeader,
+    MissingColumns(Vec<Column>),
+    DuplicateColumn {
+        column: Column,
+        first: String,
+        second: String,
+    },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::MissingHeader => write!(f, "CSV文件为空，缺少标题行"),
+            SchemaError::MissingColumns(columns) => {
+                let names: Vec<&str> = columns.iter().map(|c| c.name()).collect();
+                write!(f, "缺少必需的列: {}", names.join(", "))
+            }
+            SchemaError::DuplicateColumn { column, first, second } => {
+                write!(f, "列 '{}' 和 '{}' 都对应 {}，无法确定使用哪一列", first, second, column)
+            }
+        }
+    }
+}
+
+impl Error for SchemaError {}
+
+// ---------------------------
+// 数据表结构
+// ---------------------------
+#[derive(Debug, Clone)]
+pub struct Schema {
+    required: Vec<Column>,
+    optional: Vec<Column>,
+}
+
+impl Schema {
+    // 泰坦尼克号数据: 兼容 8 列精简格式和 Kaggle 的 train.csv / test.csv。
+    // 特征列必须存在；survived 可选，没有该列时视为待预测的无标签数据。
+    pub fn titanic() -> Self {
+        Schema {
+            required: vec![
+                Column::Pclass,
+                Column::Sex,
+                Column::Age,
+                Column::Fare,
+                Column::SibSp,
+                Column::Parch,
+            ],
+            optional: vec![
+                Column::PassengerId,
+                Column::Survived,
+                Column::Name,
+                Column::Ticket,
+                Column::Cabin,
+                Column::Embarked,
+            ],
+        }
+    }
+
+    pub fn required(&self) -> &[Column] {
+        &self.required
+    }
+
+    // 根据标题行解析列位置。一次性报告所有缺失的必需列，不认识的列会被忽略。
+    pub fn resolve(&self, header: &[String]) -> Result<ColumnMap, SchemaError> {
+        let mut indices: HashMap<Column, usize> = HashMap::new();
+        let mut ignored = Vec::new();
+
+        for (i, raw) in header.iter().enumerate() {
+            let column = Column::from_header(raw)
+                .filter(|c| self.required.contains(c) || self.optional.contains(c));
+            match column {
+                Some(column) => {
+                    if let Some(&first) = indices.get(&column) {
+                        return Err(SchemaError::DuplicateColumn {
+                            column,
+                            first: header[first].trim().to_string(),
+                            second: raw.trim().to_string(),
+                        });
+                    }
+                    indices.insert(column, i);
+                }
+                None => ignored.push(raw.trim().to_string()),
+            }
+        }
+
+        let missing: Vec<Column> = self
+            .required
+            .iter()
+            .copied()
+            .filter(|c| !indices.contains_key(c))
+            .collect();
+        if !missing.is_empty() {
+            return Err(SchemaError::MissingColumns(missing));
+        }
+
+        Ok(ColumnMap {
+            indices,
+            ignored,
+            width: header.len(),
+        })
+    }
+}
+
+// 列 -> 字段下标的映射
+#[derive(Debug, Clone)]
+pub struct ColumnMap {
+    indices: HashMap<Column, usize>,
+    ignored: Vec<String>,
+    width: usize,
+}
+
+impl ColumnMap {
+    pub fn index(&self, column: Column) -> Option<usize> {
+        self.indices.get(&column).copied()
+    }
+
+    pub fn contains(&self, column: Column) -> bool {
+        self.indices.contains_key(&column)
+    }
+
+    // 取某条记录中该列的值；列不存在或记录字段不够时返回 None
+    pub fn get<'a>(&self, fields: &'a [String], column: Column) -> Option<&'a str> {
+        self.index(column)
+            .and_then(|i| fields.get(i))
+            .map(|s| s.as_str())
+    }
+
+    // 同 get，但空字段也视为缺失
+    pub fn value<'a>(&self, fields: &'a [String], column: Column) -> Option<&'a str> {
+        self.get(fields, column).map(str::trim).filter(|s| !s.is_empty())
+    }
+
+    // 标题行中被忽略的额外列
+    pub fn ignored(&self) -> &[String] {
+        &self.ignored
+    }
+
+    // 标题行的字段数
+    pub fn width(&self) -> usize {
+        self.width
+    }
+}
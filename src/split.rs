@@ -0,0 +1,303 @@
+use std::collections::BTreeMap;
+
+use crate::dataset::{feature_index, DataSet};
+use crate::schema::Column;
+use crate::text::surname;
+use crate::rng::Rng;
+
+// ---------------------------
+// 训练/测试拆分
+// ---------------------------
+// 按 seed 随机打乱后取 test_ratio 比例作为测试集；同样的 seed 得到同样的拆分。
+// 两部分内部保持原始行顺序。
+pub fn train_test_split(dataset: &DataSet, test_ratio: f64, seed: u64) -> (DataSet, DataSet) {
+    let n = dataset.len();
+    let test_size = (n as f64 * test_ratio) as usize;
+
+    let mut rng = Rng::new(seed);
+    let mut indices: Vec<usize> = (0..n).collect();
+    rng.shuffle(&mut indices);
+
+    let mut test_indices = indices[..test_size].to_vec();
+    let mut train_indices = indices[test_size..].to_vec();
+    test_indices.sort_unstable();
+    train_indices.sort_unstable();
+
+    (dataset.subset(&train_indices), dataset.subset(&test_indices))
+}
+
+// ---------------------------
+// 分层拆分
+// ---------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Strata {
+    Label,          // 只按 survived 分层
+    LabelPclassSex, // 按 survived × pclass × sex 组合分层
+}
+
+impl Strata {
+    // 每一行所属的层
+    pub fn keys(&self, dataset: &DataSet) -> Vec<String> {
+        let pclass = feature_index("pclass").unwrap();
+        let sex = feature_index("sex").unwrap();
+        (0..dataset.len())
+            .map(|i| {
+                let label = dataset.labels.get(i).copied().unwrap_or(f64::NAN);
+                match self {
+                    Strata::Label => format!("{}", label),
+                    Strata::LabelPclassSex => {
+                        let x = dataset.features.row(i);
+                        format!("{}|{}|{}", label, x[pclass], x[sex])
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+// 按层分配测试集下标: 每层取 len * test_ratio 个，
+// 取整后剩下的名额按小数部分从大到小分给各层，使总数与 train_test_split 一致。
+pub fn stratified_indices(keys: &[String], test_ratio: f64, rng: &mut Rng) -> (Vec<usize>, Vec<usize>) {
+    let n = keys.len();
+    let test_size = (n as f64 * test_ratio) as usize;
+
+    let mut strata: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        strata.entry(key.as_str()).or_default().push(i);
+    }
+
+    let mut quotas: Vec<(usize, f64)> = strata
+        .values()
+        .map(|rows| {
+            let exact = rows.len() as f64 * test_ratio;
+            (exact.floor() as usize, exact.fract())
+        })
+        .collect();
+    let assigned: usize = quotas.iter().map(|q| q.0).sum();
+    let mut order: Vec<usize> = (0..quotas.len()).collect();
+    order.sort_by(|&a, &b| quotas[b].1.partial_cmp(&quotas[a].1).unwrap().then(a.cmp(&b)));
+    for &s in order.iter().take(test_size.saturating_sub(assigned)) {
+        quotas[s].0 += 1;
+    }
+
+    let mut train_indices = Vec::new();
+    let mut test_indices = Vec::new();
+    for (rows, (quota, _)) in strata.into_values().zip(quotas) {
+        let mut rows = rows;
+        rng.shuffle(&mut rows);
+        test_indices.extend_from_slice(&rows[..quota]);
+        train_indices.extend_from_slice(&rows[quota..]);
+    }
+    train_indices.sort_unstable();
+    test_indices.sort_unstable();
+    (train_indices, test_indices)
+}
+
+// 分层版 train_test_split: 两部分的类别比例（或组合层比例）与整体一致
+pub fn stratified_split(dataset: &DataSet, test_ratio: f64, seed: u64, strata: Strata) -> (DataSet, DataSet) {
+    let keys = strata.keys(dataset);
+    let mut rng = Rng::new(seed);
+    let (train_indices, test_indices) = stratified_indices(&keys, test_ratio, &mut rng);
+    (dataset.subset(&train_indices), dataset.subset(&test_indices))
+}
+
+// ---------------------------
+// 交叉验证折
+// ---------------------------
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fold {
+    pub train: Vec<usize>,
+    pub test: Vec<usize>,
+}
+
+// 每组下标依次作为测试集，其余作为训练集
+fn folds_from_groups(n: usize, groups: Vec<Vec<usize>>) -> Vec<Fold> {
+    groups
+        .into_iter()
+        .map(|mut test| {
+            test.sort_unstable();
+            let mut in_test = vec![false; n];
+            for &i in &test {
+                in_test[i] = true;
+            }
+            let train = (0..n).filter(|&i| !in_test[i]).collect();
+            Fold { train, test }
+        })
+        .collect()
+}
+
+// 把下标顺序切成 k 段，前 n % k 段各多一个
+fn chunk_k(order: &[usize], k: usize) -> Vec<Vec<usize>> {
+    let n = order.len();
+    let mut groups = Vec::with_capacity(k);
+    let mut start = 0;
+    for f in 0..k {
+        let size = n / k + usize::from(f < n % k);
+        groups.push(order[start..start + size].to_vec());
+        start += size;
+    }
+    groups
+}
+
+// K 折: rng 为 None 时按原始顺序切分
+pub fn k_fold(n: usize, k: usize, rng: Option<&mut Rng>) -> Vec<Fold> {
+    assert!(k >= 2 && k <= n, "k 折交叉验证要求 2 <= k <= 样本数");
+    let mut order: Vec<usize> = (0..n).collect();
+    if let Some(rng) = rng {
+        rng.shuffle(&mut order);
+    }
+    folds_from_groups(n, chunk_k(&order, k))
+}
+
+// 分层 K 折: 每层内打乱后轮流分到各折，使每折的层比例接近整体
+pub fn stratified_k_fold(keys: &[String], k: usize, rng: &mut Rng) -> Vec<Fold> {
+    let n = keys.len();
+    assert!(k >= 2 && k <= n, "k 折交叉验证要求 2 <= k <= 样本数");
+
+    let mut strata: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, key) in keys.iter().enumerate() {
+        strata.entry(key.as_str()).or_default().push(i);
+    }
+
+    let mut groups = vec![Vec::new(); k];
+    let mut next = 0;
+    for rows in strata.into_values() {
+        let mut rows = rows;
+        rng.shuffle(&mut rows);
+        for i in rows {
+            groups[next].push(i);
+            next = (next + 1) % k;
+        }
+    }
+    folds_from_groups(n, groups)
+}
+
+// 留一法: n 折，每折只有一个测试样本
+pub fn leave_one_out(n: usize) -> Vec<Fold> {
+    folds_from_groups(n, (0..n).map(|i| vec![i]).collect())
+}
+
+// ---------------------------
+// 按组拆分
+// ---------------------------
+// 同一家庭、同一张船票的乘客生还结果高度相关，拆分时必须放在同一侧，否则会泄漏信息
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupKey {
+    Surname,        // name 中逗号前的姓氏
+    Ticket,         // 船票号
+    Column(Column), // 任意原始列的取值
+}
+
+impl GroupKey {
+    // 每一行所属的组。取值缺失（或数据中没有该列）的行各自单独成组。
+    pub fn keys(&self, dataset: &DataSet) -> Vec<String> {
+        let column = match self {
+            GroupKey::Surname => Column::Name,
+            GroupKey::Ticket => Column::Ticket,
+            GroupKey::Column(column) => *column,
+        };
+        (0..dataset.len())
+            .map(|i| {
+                let raw = dataset.raw.get(i, column).map(str::trim).unwrap_or("");
+                if raw.is_empty() {
+                    return format!("#{}", i);
+                }
+                match self {
+                    // "Braund, Mr. Owen Harris" -> "braund"，没有逗号时整个姓名作为姓氏
+                    GroupKey::Surname => surname(raw).unwrap_or(raw).to_lowercase(),
+                    GroupKey::Ticket => raw.to_uppercase(),
+                    GroupKey::Column(_) => raw.to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+// 按组 K 折: 组从大到小依次放进当前样本最少的折，各折大小尽量均衡。结果是确定的。
+pub fn group_k_fold(groups: &[String], k: usize) -> Vec<Fold> {
+    let n = groups.len();
+    let mut members: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, g) in groups.iter().enumerate() {
+        members.entry(g.as_str()).or_default().push(i);
+    }
+    assert!(k >= 2 && k <= members.len(), "按组 K 折要求 2 <= k <= 组数");
+
+    let mut by_size: Vec<Vec<usize>> = members.into_values().collect();
+    by_size.sort_by_key(|rows| std::cmp::Reverse(rows.len())); // 稳定排序，同样大小按组名顺序
+
+    let mut folds = vec![Vec::new(); k];
+    for rows in by_size {
+        let smallest = (0..k).min_by_key(|&f| folds[f].len()).unwrap();
+        folds[smallest].extend(rows);
+    }
+    folds_from_groups(n, folds)
+}
+
+// 按组随机拆分: 每次随机抽取 ceil(test_ratio * 组数) 个组作为测试集
+pub fn group_shuffle_split(groups: &[String], test_ratio: f64, n_splits: usize, rng: &mut Rng) -> Vec<Fold> {
+    let n = groups.len();
+    let mut members: BTreeMap<&str, Vec<usize>> = BTreeMap::new();
+    for (i, g) in groups.iter().enumerate() {
+        members.entry(g.as_str()).or_default().push(i);
+    }
+    let members: Vec<Vec<usize>> = members.into_values().collect();
+    let n_test = ((members.len() as f64 * test_ratio).ceil() as usize).min(members.len());
+
+    let splits = (0..n_splits)
+        .map(|_| {
+            rng.sample_without_replacement(members.len(), n_test)
+                .into_iter()
+                .flat_map(|g| members[g].iter().copied())
+                .collect()
+        })
+        .collect();
+    folds_from_groups(n, splits)
+}
+
+// 按组版 train_test_split: 同一组的乘客不会同时出现在训练集和测试集
+pub fn group_train_test_split(dataset: &DataSet, test_ratio: f64, seed: u64, key: GroupKey) -> (DataSet, DataSet) {
+    let groups = key.keys(dataset);
+    let fold = group_shuffle_split(&groups, test_ratio, 1, &mut Rng::new(seed)).remove(0);
+    (dataset.subset(&fold.train), dataset.subset(&fold.test))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CvStrategy {
+    KFold { k: usize, seed: Option<u64> }, // seed 为 None 时不打乱
+    StratifiedKFold { k: usize, seed: u64, strata: Strata },
+    // 用不同的随机划分重复 K 折，strata 不为 None 时每次都分层
+    RepeatedKFold { k: usize, repeats: usize, seed: u64, strata: Option<Strata> },
+    LeaveOneOut,
+    GroupKFold { k: usize, key: GroupKey },
+    GroupShuffleSplit { n_splits: usize, test_ratio: f64, seed: u64, key: GroupKey },
+}
+
+impl CvStrategy {
+    pub fn folds(&self, dataset: &DataSet) -> Vec<Fold> {
+        let n = dataset.len();
+        match *self {
+            CvStrategy::KFold { k, seed } => {
+                let mut rng = seed.map(Rng::new);
+                k_fold(n, k, rng.as_mut())
+            }
+            CvStrategy::StratifiedKFold { k, seed, strata } => {
+                stratified_k_fold(&strata.keys(dataset), k, &mut Rng::new(seed))
+            }
+            CvStrategy::RepeatedKFold { k, repeats, seed, strata } => {
+                let mut rng = Rng::new(seed);
+                let keys = strata.map(|s| s.keys(dataset));
+                (0..repeats)
+                    .flat_map(|_| match &keys {
+                        Some(keys) => stratified_k_fold(keys, k, &mut rng),
+                        None => k_fold(n, k, Some(&mut rng)),
+                    })
+                    .collect()
+            }
+            CvStrategy::LeaveOneOut => leave_one_out(n),
+            CvStrategy::GroupKFold { k, key } => group_k_fold(&key.keys(dataset), k),
+            CvStrategy::GroupShuffleSplit { n_splits, test_ratio, seed, key } => {
+                group_shuffle_split(&key.keys(dataset), test_ratio, n_splits, &mut Rng::new(seed))
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+
+use crate::frame::{DataFrame, FrameError, Series};
+use crate::schema::Column;
+
+// ---------------------------
+// 从姓名中提取特征
+// ---------------------------
+// Kaggle 数据的姓名格式为 "姓, 称谓. 名 (娘家全名)"，例如
+// "Cumings, Mrs. John Bradley (Florence Briggs Thayer)"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Title {
+    Mr,
+    Mrs,
+    Miss,
+    Master,
+    Rev,
+    Dr,
+    Rare, // 出现很少的称谓归为一类: Col、Major、Capt、Sir、Lady、Countess、Don、Jonkheer 等
+}
+
+impl Title {
+    pub const ALL: [Title; 7] = [
+        Title::Mr,
+        Title::Mrs,
+        Title::Miss,
+        Title::Master,
+        Title::Rev,
+        Title::Dr,
+        Title::Rare,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Title::Mr => "Mr",
+            Title::Mrs => "Mrs",
+            Title::Miss => "Miss",
+            Title::Master => "Master",
+            Title::Rev => "Rev",
+            Title::Dr => "Dr",
+            Title::Rare => "Rare",
+        }
+    }
+
+    // 法语等写法归并到对应的常见称谓；无法识别的称谓归为 Rare，没有称谓时返回 None
+    pub fn from_name(name: &str) -> Option<Title> {
+        let (_, rest) = name.split_once(',')?;
+        let (title, _) = rest.split_once('.')?;
+        let title = title.trim().to_lowercase();
+        let title = title.strip_prefix("the ").unwrap_or(&title).trim();
+        if title.is_empty() {
+            return None;
+        }
+        Some(match title {
+            "mr" => Title::Mr,
+            "mrs" | "mme" => Title::Mrs,
+            "miss" | "mlle" | "ms" => Title::Miss,
+            "master" => Title::Master,
+            "rev" => Title::Rev,
+            "dr" => Title::Dr,
+            _ => Title::Rare,
+        })
+    }
+}
+
+impl fmt::Display for Title {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+// 逗号前的姓氏，没有逗号时返回 None
+pub fn surname(name: &str) -> Option<&str> {
+    let (surname, _) = name.split_once(',')?;
+    let surname = surname.trim();
+    if surname.is_empty() {
+        None
+    } else {
+        Some(surname)
+    }
+}
+
+// 姓名长度（字符数），较长的姓名往往意味着较高的社会地位
+pub fn name_length(name: &str) -> usize {
+    name.trim().chars().count()
+}
+
+// 括号里带有娘家全名，通常是已婚女性
+pub fn has_maiden_name(name: &str) -> bool {
+    matches!(name.find('('), Some(open) if name[open..].contains(')'))
+}
+
+// 在乘客表中增加 title、surname（分类列）、name_length 和 has_maiden_name 列，姓名缺失时为空值
+pub fn add_name_features(frame: &mut DataFrame) -> Result<(), FrameError> {
+    let names = frame.column(Column::Name.name())?;
+    let names: Vec<Option<&str>> = (0..frame.height()).map(|i| names.str_at(i)).collect();
+
+    let titles = names
+        .iter()
+        .map(|name| name.and_then(Title::from_name).map(|t| Title::ALL.iter().position(|&a| a == t).unwrap() as u32))
+        .collect();
+    let dictionary = Title::ALL.iter().map(|t| t.label().to_string()).collect();
+    let title = Series::from_codes("title", titles, dictionary);
+
+    let surnames: Vec<Option<&str>> = names.iter().map(|name| name.and_then(surname)).collect();
+    let surname = Series::categorical("surname", &surnames);
+
+    let lengths = names.iter().map(|name| name.map(|n| name_length(n) as i64)).collect();
+    let maiden = names.iter().map(|name| name.map(has_maiden_name)).collect();
+
+    frame.add_column(title)?;
+    frame.add_column(surname)?;
+    frame.add_column(Series::from_i64("name_length", lengths))?;
+    frame.add_column(Series::from_bool("has_maiden_name", maiden))?;
+    Ok(())
+}
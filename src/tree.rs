@@ -0,0 +1,664 @@
+use std::cmp::Ordering;
+
+use crate::hist::{BinnedMatrix, Histogram};
+use crate::matrix::Matrix;
+use crate::rng::Rng;
+
+// ---------------------------
+// CART 决策树
+// ---------------------------
+// 每次分裂随机抽取的候选特征数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MaxFeatures {
+    All,
+    Sqrt,
+    Log2,
+    Fraction(f64), // 特征总数的比例
+    Count(usize),
+}
+
+impl MaxFeatures {
+    // 换算成 n 个特征中的候选数，至少 1 个、至多 n 个
+    pub fn resolve(self, n: usize) -> usize {
+        let k = match self {
+            MaxFeatures::All => n,
+            MaxFeatures::Sqrt => (n as f64).sqrt().round() as usize,
+            MaxFeatures::Log2 => (n as f64).log2().round() as usize,
+            MaxFeatures::Fraction(f) => (f * n as f64).round() as usize,
+            MaxFeatures::Count(k) => k,
+        };
+        k.clamp(1, n.max(1))
+    }
+}
+
+// 节点不纯度的度量，参数是节点上的正类数和样本数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Criterion {
+    Gini,    // 1 - p² - (1 - p)²
+    Entropy, // 以 2 为底的香农熵
+    LogLoss, // 用节点正类比例作预测时的平均对数损失，即以 e 为底的熵
+}
+
+impl Criterion {
+    pub fn impurity(self, positive: f64, total: f64) -> f64 {
+        if total == 0.0 {
+            return 0.0;
+        }
+        let p = positive / total;
+        let entropy = |log: fn(f64) -> f64| {
+            [p, 1.0 - p].iter().filter(|&&q| q > 0.0).map(|&q| -q * log(q)).sum::<f64>()
+        };
+        match self {
+            Criterion::Gini => 2.0 * p * (1.0 - p),
+            Criterion::Entropy => entropy(f64::log2),
+            Criterion::LogLoss => entropy(f64::ln),
+        }
+    }
+}
+
+// 寻找分裂的方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Splitter {
+    Exact,                        // 在所有相邻的不同取值之间尝试，训练前每个特征排序一次
+    Histogram { max_bins: usize }, // 先把特征分箱（至多 255 箱），只在箱的边界上尝试，适合大数据
+}
+
+// 生长的停止条件，默认值与 sklearn 相同：不限深度，长到叶子纯净为止
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TreeParams {
+    pub max_depth: Option<usize>,    // 根节点深度为 0
+    pub min_samples_split: usize,    // 样本数少于它的节点不再分裂
+    pub min_samples_leaf: usize,     // 分裂后每个子节点至少的样本数
+    pub min_impurity_decrease: f64,  // 加权不纯度下降 (n_t / n) * Δ 低于它的分裂不做
+    pub max_leaf_nodes: Option<usize>, // 设置后按不纯度下降从大到小优先分裂
+    pub max_features: MaxFeatures,
+    pub criterion: Criterion,
+    pub splitter: Splitter,
+}
+
+impl Default for TreeParams {
+    fn default() -> Self {
+        TreeParams {
+            max_depth: None,
+            min_samples_split: 2,
+            min_samples_leaf: 1,
+            min_impurity_decrease: 0.0,
+            max_leaf_nodes: None,
+            max_features: MaxFeatures::All,
+            criterion: Criterion::Gini,
+            splitter: Splitter::Exact,
+        }
+    }
+}
+
+// 节点存放在 DecisionTree::nodes 里，子节点用下标引用，根节点是 nodes[0]。
+// value 是节点上训练样本中正类（生还）的比例，叶子的 value 就是预测概率。
+#[derive(Debug, Clone, PartialEq)]
+pub enum Node {
+    Leaf {
+        value: f64,
+        samples: usize,
+    },
+    Split {
+        feature: usize,
+        threshold: f64, // x[feature] <= threshold 走左子树，NaN 走右子树
+        left: usize,
+        right: usize,
+        value: f64,
+        samples: usize,
+        impurity_decrease: f64, // 加权的不纯度下降，用于计算特征重要性
+    },
+}
+
+impl Node {
+    pub fn value(&self) -> f64 {
+        match *self {
+            Node::Leaf { value, .. } | Node::Split { value, .. } => value,
+        }
+    }
+
+    pub fn samples(&self) -> usize {
+        match *self {
+            Node::Leaf { samples, .. } | Node::Split { samples, .. } => samples,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DecisionTree {
+    nodes: Vec<Node>,
+    n_features: usize,
+}
+
+// 节点上的样本。orders[f] 是同一批行下标按第 f 个特征升序（NaN 在最后）排好的顺序，
+// 只在根节点排序一次，分裂时稳定地划分到两个子节点，子节点不用重新排序；
+// 不会再分裂的子节点不需要排序，orders 为空
+struct Samples {
+    rows: Vec<usize>,
+    orders: Vec<Vec<usize>>,
+}
+
+impl Samples {
+    fn root(x: &Matrix) -> Self {
+        let rows: Vec<usize> = (0..x.rows()).collect();
+        let orders = (0..x.cols())
+            .map(|f| {
+                let mut order = rows.clone();
+                order.sort_by(|&a, &b| nan_last(x[(a, f)], x[(b, f)]));
+                order
+            })
+            .collect();
+        Samples { rows, orders }
+    }
+
+    fn partition(self, goes_left: impl Fn(usize) -> bool, splittable: &dyn Fn(&[usize]) -> bool) -> (Samples, Samples) {
+        let (left_rows, right_rows): (Vec<usize>, Vec<usize>) = self.rows.iter().partition(|&&r| goes_left(r));
+        let orders = |side: bool| -> Vec<Vec<usize>> {
+            self.orders
+                .iter()
+                .map(|order| order.iter().copied().filter(|&r| goes_left(r) == side).collect())
+                .collect()
+        };
+        let left_orders = if splittable(&left_rows) { orders(true) } else { Vec::new() };
+        let right_orders = if splittable(&right_rows) { orders(false) } else { Vec::new() };
+        (
+            Samples { rows: left_rows, orders: left_orders },
+            Samples { rows: right_rows, orders: right_orders },
+        )
+    }
+}
+
+// 升序，NaN 排在所有数值之后
+fn nan_last(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or_else(|| a.is_nan().cmp(&b.is_nan()))
+}
+
+// 一个候选分裂
+struct SplitCandidate {
+    feature: usize,
+    threshold: f64,
+    impurity_decrease: f64,
+}
+
+// 等待分裂的叶子
+struct Pending<S> {
+    node: usize,
+    depth: usize,
+    samples: S,
+    split: SplitCandidate,
+}
+
+impl DecisionTree {
+    // 默认参数，在所有特征上寻找分裂
+    pub fn train(x: &Matrix, y: &[f64]) -> Self {
+        DecisionTree::train_with(x, y, &TreeParams::default(), &mut Rng::new(0))
+    }
+
+    // rng 只用于按 max_features 抽取候选特征
+    pub fn train_with(x: &Matrix, y: &[f64], params: &TreeParams, rng: &mut Rng) -> Self {
+        assert_eq!(x.rows(), y.len(), "样本数 {} 与标签数 {} 不一致", x.rows(), y.len());
+        let n_features = x.cols();
+        if x.is_empty() {
+            return DecisionTree {
+                nodes: vec![Node::Leaf { value: 0.0, samples: 0 }],
+                n_features,
+            };
+        }
+
+        let builder = TreeBuilder {
+            y,
+            params,
+            n_features,
+            max_features: params.max_features.resolve(n_features),
+            n_total: x.rows() as f64,
+        };
+        let nodes = match params.splitter {
+            Splitter::Exact => builder.grow(&ExactFinder { x, y }, rng),
+            Splitter::Histogram { max_bins } => {
+                let binned = BinnedMatrix::new(x, max_bins);
+                builder.grow(&HistogramFinder { binned: &binned, y }, rng)
+            }
+        };
+
+        DecisionTree { nodes, n_features }
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn n_leaves(&self) -> usize {
+        self.nodes.iter().filter(|n| matches!(n, Node::Leaf { .. })).count()
+    }
+
+    // 只有根节点时深度为 0
+    pub fn depth(&self) -> usize {
+        fn depth_of(nodes: &[Node], i: usize) -> usize {
+            match nodes[i] {
+                Node::Leaf { .. } => 0,
+                Node::Split { left, right, .. } => 1 + depth_of(nodes, left).max(depth_of(nodes, right)),
+            }
+        }
+        depth_of(&self.nodes, 0)
+    }
+
+    // 各特征的不纯度下降之和，归一化到总和为 1（没有分裂时全为 0）
+    pub fn feature_importances(&self) -> Vec<f64> {
+        let mut totals = vec![0.0; self.n_features];
+        for node in &self.nodes {
+            if let Node::Split { feature, impurity_decrease, .. } = *node {
+                totals[feature] += impurity_decrease;
+            }
+        }
+        let sum: f64 = totals.iter().sum();
+        if sum > 0.0 {
+            totals.iter_mut().for_each(|t| *t /= sum);
+        }
+        totals
+    }
+
+    // 样本落入的叶子中正类的比例
+    pub fn predict_proba(&self, x: &[f64]) -> f64 {
+        let mut i = 0;
+        loop {
+            match self.nodes[i] {
+                Node::Leaf { value, .. } => return value,
+                Node::Split { feature, threshold, left, right, .. } => {
+                    i = if x[feature] <= threshold { left } else { right };
+                }
+            }
+        }
+    }
+
+    pub fn predict(&self, x: &[f64]) -> f64 {
+        if self.predict_proba(x) >= 0.5 { 1.0 } else { 0.0 }
+    }
+
+    // 缩进的文本形式，例如
+    // sex <= 0.5 (n=571)
+    //   pclass=3 <= 0.5 (n=368)
+    //     leaf p=0.150 (n=120)
+    pub fn describe(&self, feature_names: &[String]) -> String {
+        let mut out = String::new();
+        let mut stack = vec![(0, 0)];
+        while let Some((i, depth)) = stack.pop() {
+            let indent = "  ".repeat(depth);
+            match self.nodes[i] {
+                Node::Leaf { value, samples } => {
+                    out.push_str(&format!("{}leaf p={:.3} (n={})\n", indent, value, samples));
+                }
+                Node::Split { feature, threshold, left, right, samples, .. } => {
+                    let name = feature_names.get(feature).cloned().unwrap_or_else(|| format!("x{}", feature));
+                    out.push_str(&format!("{}{} <= {} (n={})\n", indent, name, threshold, samples));
+                    stack.push((right, depth + 1));
+                    stack.push((left, depth + 1));
+                }
+            }
+        }
+        out
+    }
+}
+
+// 训练时共享的数据和参数
+struct TreeBuilder<'a> {
+    y: &'a [f64],
+    params: &'a TreeParams,
+    n_features: usize,
+    max_features: usize,
+    n_total: f64,
+}
+
+// 节点的样本数、正类数和不纯度
+#[derive(Clone, Copy)]
+struct NodeStats {
+    n: usize,
+    positive: f64,
+    impurity: f64,
+}
+
+// 两种分裂方式的差别只在于节点上保存什么、怎样在一个特征上找最佳阈值、怎样把样本分给子节点
+trait SplitFinder {
+    type Samples;
+
+    fn root(&self) -> Self::Samples;
+
+    fn rows<'s>(&self, samples: &'s Self::Samples) -> &'s [usize];
+
+    // 在 feature 上找最佳阈值，返回 (阈值, 加权不纯度下降)
+    fn best_threshold(
+        &self,
+        builder: &TreeBuilder,
+        samples: &Self::Samples,
+        stats: NodeStats,
+        feature: usize,
+    ) -> Option<(f64, f64)>;
+
+    // splittable 判断子节点是否还可能分裂，不能分裂的子节点只保留行下标
+    fn partition(
+        &self,
+        samples: Self::Samples,
+        split: &SplitCandidate,
+        splittable: &dyn Fn(&[usize]) -> bool,
+    ) -> (Self::Samples, Self::Samples);
+}
+
+impl TreeBuilder<'_> {
+    fn leaf(&self, indices: &[usize]) -> Node {
+        let positive: f64 = indices.iter().map(|&i| self.y[i]).sum();
+        let value = if indices.is_empty() { 0.0 } else { positive / indices.len() as f64 };
+        Node::Leaf { value, samples: indices.len() }
+    }
+
+    // 限制叶子数时每次分裂不纯度下降最大的叶子（best-first）。不限制叶子数时顺序不影响最终结构，
+    // 按深度优先用栈分裂，同时等待分裂的节点最多约为树深，节点上的排序或直方图不会堆积
+    fn grow<F: SplitFinder>(&self, finder: &F, rng: &mut Rng) -> Vec<Node> {
+        let root = finder.root();
+        let mut nodes = vec![self.leaf(finder.rows(&root))];
+        let mut pending: Vec<Pending<F::Samples>> = Vec::new();
+        if let Some(split) = self.best_split(finder, &root, 0, rng) {
+            pending.push(Pending { node: 0, depth: 0, samples: root, split });
+        }
+
+        let mut n_leaves = 1;
+        while !pending.is_empty() {
+            let next = match self.params.max_leaf_nodes {
+                Some(max) if n_leaves >= max => break,
+                Some(_) => (0..pending.len())
+                    .max_by(|&a, &b| {
+                        let (da, db) = (pending[a].split.impurity_decrease, pending[b].split.impurity_decrease);
+                        da.total_cmp(&db).then(pending[b].node.cmp(&pending[a].node)) // 并列时先分裂先创建的节点
+                    })
+                    .expect("pending 非空"),
+                None => pending.len() - 1,
+            };
+            let Pending { node, depth, samples, split } = pending.swap_remove(next);
+            let splittable = |rows: &[usize]| self.node_stats(rows, depth + 1).is_some();
+            let (left_samples, right_samples) = finder.partition(samples, &split, &splittable);
+
+            let (left, right) = (nodes.len(), nodes.len() + 1);
+            nodes.push(self.leaf(finder.rows(&left_samples)));
+            nodes.push(self.leaf(finder.rows(&right_samples)));
+            let (value, samples) = (nodes[node].value(), nodes[node].samples());
+            nodes[node] = Node::Split {
+                feature: split.feature,
+                threshold: split.threshold,
+                left,
+                right,
+                value,
+                samples,
+                impurity_decrease: split.impurity_decrease,
+            };
+            n_leaves += 1;
+
+            // 右子节点先入栈，深度优先时先分裂左子节点
+            for (child, samples) in [(right, right_samples), (left, left_samples)] {
+                if let Some(split) = self.best_split(finder, &samples, depth + 1, rng) {
+                    pending.push(Pending { node: child, depth: depth + 1, samples, split });
+                }
+            }
+        }
+        nodes
+    }
+
+    // 节点上的最佳分裂；不满足停止条件或找不到满足约束的分裂时返回 None。
+    // 得分严格更高才替换，特征按下标、阈值从小到大尝试，并列时取最先遇到的，结果是确定的。
+    fn best_split<F: SplitFinder>(
+        &self,
+        finder: &F,
+        samples: &F::Samples,
+        depth: usize,
+        rng: &mut Rng,
+    ) -> Option<SplitCandidate> {
+        let stats = self.node_stats(finder.rows(samples), depth)?;
+        let mut best: Option<SplitCandidate> = None;
+        let mut features = rng.sample_without_replacement(self.n_features, self.max_features);
+        features.sort_unstable(); // 与抽样顺序无关
+        for feature in features {
+            if let Some((threshold, impurity_decrease)) = finder.best_threshold(self, samples, stats, feature) {
+                if best.as_ref().is_none_or(|b| impurity_decrease > b.impurity_decrease) {
+                    best = Some(SplitCandidate { feature, threshold, impurity_decrease });
+                }
+            }
+        }
+
+        best.filter(|b| b.impurity_decrease >= self.params.min_impurity_decrease)
+    }
+
+    // 节点的统计量；纯节点、样本太少或达到最大深度时不再分裂，返回 None
+    fn node_stats(&self, rows: &[usize], depth: usize) -> Option<NodeStats> {
+        let params = self.params;
+        let n = rows.len();
+        let positive: f64 = rows.iter().map(|&r| self.y[r]).sum();
+        let impurity = params.criterion.impurity(positive, n as f64);
+        if impurity == 0.0
+            || n < params.min_samples_split.max(2)
+            || n < 2 * params.min_samples_leaf
+            || params.max_depth.is_some_and(|max| depth >= max)
+        {
+            return None;
+        }
+        Some(NodeStats { n, positive, impurity })
+    }
+
+    // 左侧有 left_total 个样本、left_count 个正类时的加权不纯度下降；违反 min_samples_leaf 时为 None
+    fn score(&self, stats: NodeStats, left_total: usize, left_count: f64) -> Option<f64> {
+        let criterion = self.params.criterion;
+        let min_leaf = self.params.min_samples_leaf.max(1);
+        let right_total = stats.n - left_total;
+        if left_total < min_leaf || right_total < min_leaf {
+            return None;
+        }
+        let children = (left_total as f64 * criterion.impurity(left_count, left_total as f64)
+            + right_total as f64 * criterion.impurity(stats.positive - left_count, right_total as f64))
+            / stats.n as f64;
+        Some(stats.n as f64 / self.n_total * (stats.impurity - children))
+    }
+}
+
+// 精确分裂：每个特征按排好的顺序扫一遍，维护左侧的样本数和正类数，
+// 在相邻的不同取值之间取中点作阈值，所以每个特征是 O(n) 而不是逐个阈值重新扫描
+struct ExactFinder<'a> {
+    x: &'a Matrix,
+    y: &'a [f64],
+}
+
+impl SplitFinder for ExactFinder<'_> {
+    type Samples = Samples;
+
+    fn root(&self) -> Samples {
+        Samples::root(self.x)
+    }
+
+    fn rows<'s>(&self, samples: &'s Samples) -> &'s [usize] {
+        &samples.rows
+    }
+
+    fn best_threshold(&self, builder: &TreeBuilder, samples: &Samples, stats: NodeStats, f: usize) -> Option<(f64, f64)> {
+        let order = &samples.orders[f];
+        let mut best: Option<(f64, f64)> = None;
+        let mut left_count = 0.0;
+        for k in 0..stats.n - 1 {
+            let value = self.x[(order[k], f)];
+            if value.is_nan() {
+                break; // 之后全是 NaN，总是分到右侧
+            }
+            left_count += self.y[order[k]];
+            let next = self.x[(order[k + 1], f)];
+            if next <= value {
+                continue; // 相同的取值不能分开
+            }
+            let Some(decrease) = builder.score(stats, k + 1, left_count) else {
+                continue;
+            };
+            if best.is_none_or(|(_, d)| decrease > d) {
+                // 中点可能因舍入等于 next，此时退回 value，保证 value <= 阈值 < next
+                let midpoint = value + (next - value) / 2.0;
+                let threshold = if next.is_nan() || midpoint >= next { value } else { midpoint };
+                best = Some((threshold, decrease));
+            }
+        }
+        best
+    }
+
+    fn partition(&self, samples: Samples, split: &SplitCandidate, splittable: &dyn Fn(&[usize]) -> bool) -> (Samples, Samples) {
+        samples.partition(|r| self.x[(r, split.feature)] <= split.threshold, splittable)
+    }
+}
+
+// 直方图分裂：节点上保存各特征的箱直方图，阈值只取箱的边界，
+// 每个特征的扫描是 O(箱数)；分裂时只为较小的子节点建直方图，另一个用父节点减去它。
+// 不会再分裂的子节点不保存直方图
+struct HistogramFinder<'a> {
+    binned: &'a BinnedMatrix,
+    y: &'a [f64],
+}
+
+struct BinnedSamples {
+    rows: Vec<usize>,
+    histogram: Option<Histogram>,
+}
+
+impl SplitFinder for HistogramFinder<'_> {
+    type Samples = BinnedSamples;
+
+    fn root(&self) -> BinnedSamples {
+        let rows: Vec<usize> = (0..self.binned.rows()).collect();
+        let histogram = Some(Histogram::build(self.binned, self.y, &rows));
+        BinnedSamples { rows, histogram }
+    }
+
+    fn rows<'s>(&self, samples: &'s BinnedSamples) -> &'s [usize] {
+        &samples.rows
+    }
+
+    fn best_threshold(
+        &self,
+        builder: &TreeBuilder,
+        samples: &BinnedSamples,
+        stats: NodeStats,
+        f: usize,
+    ) -> Option<(f64, f64)> {
+        let bins = samples.histogram.as_ref().expect("可分裂的节点保存了直方图").feature(f);
+        let edges = self.binned.edges(f);
+        let mut best: Option<(f64, f64)> = None;
+        let (mut left_total, mut left_count) = (0.0, 0.0);
+        // 在第 b 箱之后切开；缺失值的箱总是在右侧。在最后一个非缺失箱之后切开就是把缺失值单独分到右侧，
+        // 与精确分裂在最大取值和 NaN 之间切开相同
+        for (b, bin) in bins.iter().enumerate().take(self.binned.n_bins(f)) {
+            left_total += bin.count;
+            left_count += bin.sum;
+            if bin.count == 0.0 {
+                continue; // 与上一个边界的切分相同
+            }
+            let Some(decrease) = builder.score(stats, left_total as usize, left_count) else {
+                continue;
+            };
+            if best.is_none_or(|(_, d)| decrease > d) {
+                best = Some((edges.get(b).copied().unwrap_or(f64::INFINITY), decrease));
+            }
+        }
+        best
+    }
+
+    fn partition(
+        &self,
+        samples: BinnedSamples,
+        split: &SplitCandidate,
+        splittable: &dyn Fn(&[usize]) -> bool,
+    ) -> (BinnedSamples, BinnedSamples) {
+        let bin = self.binned.code(split.feature, split.threshold);
+        let codes = self.binned.column(split.feature);
+        let (left, right): (Vec<usize>, Vec<usize>) = samples.rows.iter().partition(|&&r| codes[r] <= bin);
+        let left_is_small = left.len() <= right.len();
+        let (small_rows, large_rows) = if left_is_small { (&left, &right) } else { (&right, &left) };
+        let (keep_small, keep_large) = (splittable(small_rows), splittable(large_rows));
+        // 只有较大的子节点需要时，也是先建较小的再相减
+        let small = (keep_small || keep_large).then(|| Histogram::build(self.binned, self.y, small_rows));
+        let large = match (&small, &samples.histogram) {
+            (Some(small), Some(parent)) if keep_large => Some(parent.subtract(small)),
+            _ => None,
+        };
+        let small = small.filter(|_| keep_small);
+        let (left_histogram, right_histogram) = if left_is_small { (small, large) } else { (large, small) };
+        (
+            BinnedSamples { rows: left, histogram: left_histogram },
+            BinnedSamples { rows: right, histogram: right_histogram },
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hist::MAX_BINS;
+
+    // 取值很少的整数特征（第 2 列约一成缺失），标签由前两列决定并带噪声
+    fn data(n: usize, seed: u64) -> (Matrix, Vec<f64>) {
+        let mut rng = Rng::new(seed);
+        let mut x = Matrix::zeros(n, 3);
+        let mut y = Vec::with_capacity(n);
+        for i in 0..n {
+            let row = x.row_mut(i);
+            row[0] = rng.gen_range(12) as f64;
+            row[1] = rng.gen_range(5) as f64;
+            row[2] = if rng.next_f64() < 0.1 { f64::NAN } else { rng.gen_range(20) as f64 };
+            let p = if row[0] + 2.0 * row[1] > 10.0 { 0.8 } else { 0.25 };
+            y.push(if rng.next_f64() < p { 1.0 } else { 0.0 });
+        }
+        (x, y)
+    }
+
+    // 先序遍历的 (分裂特征, 样本数, 叶子值)，不比较阈值：精确分裂取节点内相邻取值的中点，
+    // 直方图取全局相邻取值的中点，两者不同但划分相同
+    fn shape(tree: &DecisionTree) -> Vec<(Option<usize>, usize, f64)> {
+        let mut out = Vec::new();
+        let mut stack = vec![0];
+        while let Some(i) = stack.pop() {
+            let node = &tree.nodes()[i];
+            match *node {
+                Node::Leaf { .. } => out.push((None, node.samples(), node.value())),
+                Node::Split { feature, left, right, .. } => {
+                    out.push((Some(feature), node.samples(), node.value()));
+                    stack.push(right);
+                    stack.push(left);
+                }
+            }
+        }
+        out
+    }
+
+    fn train(x: &Matrix, y: &[f64], params: &TreeParams) -> DecisionTree {
+        DecisionTree::train_with(x, y, params, &mut Rng::new(7))
+    }
+
+    #[test]
+    fn histogram_with_enough_bins_matches_exact() {
+        let (x, y) = data(600, 1);
+        for max_leaf_nodes in [None, Some(12)] {
+            let exact = TreeParams { max_leaf_nodes, min_samples_leaf: 3, ..TreeParams::default() };
+            let exact_tree = train(&x, &y, &exact);
+            assert!(exact_tree.n_leaves() > 10);
+            // 20 是各列不同取值数的最大值
+            for max_bins in [20, MAX_BINS] {
+                let histogram = TreeParams { splitter: Splitter::Histogram { max_bins }, ..exact };
+                let histogram_tree = train(&x, &y, &histogram);
+                assert_eq!(shape(&histogram_tree), shape(&exact_tree), "max_bins={} max_leaf_nodes={:?}", max_bins, max_leaf_nodes);
+                for row in x.iter_rows() {
+                    assert_eq!(histogram_tree.predict_proba(row), exact_tree.predict_proba(row));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn depth_first_growth_matches_best_first_without_leaf_limit() {
+        let (x, y) = data(400, 2);
+        let names: Vec<String> = (0..3).map(|j| format!("x{}", j)).collect();
+        for splitter in [Splitter::Exact, Splitter::Histogram { max_bins: MAX_BINS }] {
+            let depth_first = TreeParams { splitter, ..TreeParams::default() };
+            let best_first = TreeParams { max_leaf_nodes: Some(usize::MAX), ..depth_first };
+            let (a, b) = (train(&x, &y, &depth_first), train(&x, &y, &best_first));
+            assert_eq!(a.describe(&names), b.describe(&names));
+        }
+    }
+}